@@ -1,26 +1,38 @@
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env,
-    Fraction, MessageInfo, QueryRequest, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
-    WasmQuery,
+    from_json, to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Fraction, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage,
+    SubMsg, Uint128, WasmMsg,
+};
+use cremation_token::{
+    msg::{AssetInfo, SwapDex, SwapHop},
+    swap::swap_run_msg,
 };
-use cremation_token::msg::AssetInfo;
 use cw2::set_contract_version;
-use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
 
 use crate::{
-    error::ContractError, helpers::create_swap_operations, msg::*, state::OWNER, state::*,
+    error::ContractError,
+    helpers::{create_swap_runs, create_transfer_msg, query_asset_balance},
+    msg::*,
+    state::OWNER,
+    state::*,
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = "burning";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-const SWAP_REPLY_ID: u64 = 1;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 pub const LUNC_TAX: Decimal = Decimal::permille(5);
+const DAY: u64 = 86_400;
+const WEEK: u64 = 7 * DAY;
+const MAX_STATS_DAYS: u64 = 366;
 
 pub fn instantiate(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -28,7 +40,15 @@ pub fn instantiate(
 
     let owner = deps.api.addr_validate(&msg.owner)?;
     OWNER.save(deps.storage, &owner)?;
-    BURNED_AMOUNT.save(deps.storage, &Uint128::zero())?;
+    BURNED_AMOUNTS.save(deps.storage, "uluna", &Uint128::zero())?;
+    BURNABLE_DENOMS.save(
+        deps.storage,
+        "uluna",
+        &BurnableDenom {
+            fee_ratio: None,
+            reward_weight: Decimal::one(),
+        },
+    )?;
 
     let fee_ratio = msg.development_config.fee_ratio;
     if fee_ratio >= Decimal::one() {
@@ -39,14 +59,12 @@ pub fn instantiate(
     let beneficiary = deps
         .api
         .addr_validate(&msg.development_config.beneficiary)?;
-    DEVELOPMENT_FEE_BENEFICIARY.save(deps.storage, &beneficiary)?;
-
-    CACHE.save(
+    DEVELOPMENT_FEE_BENEFICIARIES.save(
         deps.storage,
-        &CachedData {
-            locked: false,
-            burner: env.contract.address,
-        },
+        &vec![FeeBeneficiary {
+            address: beneficiary,
+            weight: 1,
+        }],
     )?;
 
     Ok(Response::default())
@@ -63,66 +81,256 @@ pub fn execute(
             fee_ratio,
             beneficiary,
         } => execute::update_development_config(deps, env, info, fee_ratio, beneficiary),
+        ExecuteMsg::SetFeeBeneficiaries { beneficiaries } => {
+            execute::set_fee_beneficiaries(deps, env, info, beneficiaries)
+        }
         ExecuteMsg::AddToRewardWhitelist { reward_info } => {
             execute::add_to_reward_whitelist(deps, env, info, reward_info)
         }
-        ExecuteMsg::RemoveFromRewardWhitelist { token } => {
-            execute::remove_from_reward_whitelist(deps, env, info, token)
+        ExecuteMsg::RemoveFromRewardWhitelist { asset_info } => {
+            execute::remove_from_reward_whitelist(deps, env, info, asset_info)
         }
         ExecuteMsg::UpdateRewardInfo { reward_info } => {
             execute::update_reward_info(deps, env, info, reward_info)
         }
-        ExecuteMsg::Burn {} => {
-            // check lunc in funds
-            let funds = info.funds.clone();
-            let mut burn_amount = Uint128::zero();
-            for coin in funds {
-                if coin.denom == "uluna" {
-                    burn_amount = coin.amount;
-                    break;
-                }
-            }
-
-            execute::burn(deps, env, info.sender, burn_amount)
+        ExecuteMsg::UpdateRewardVesting { duration } => {
+            execute::update_reward_vesting(deps, env, info, duration)
+        }
+        ExecuteMsg::ClaimRewards {} => execute::claim_rewards(deps, env, info),
+        ExecuteMsg::SetRewardTiers { basis, tiers } => {
+            execute::set_reward_tiers(deps, env, info, basis, tiers)
+        }
+        ExecuteMsg::SetReferralConfig { referral_config } => {
+            execute::set_referral_config(deps, env, info, referral_config)
+        }
+        ExecuteMsg::SetEpochDuration { duration } => {
+            execute::set_epoch_duration(deps, env, info, duration)
+        }
+        ExecuteMsg::ProcessEpoch {} => execute::process_epoch(deps, env, info),
+        ExecuteMsg::ClaimEpochRewards {} => execute::claim_epoch_rewards(deps, env, info),
+        ExecuteMsg::DepositRewards {} => execute::deposit_native_rewards(deps, env, info),
+        ExecuteMsg::SetBurnableDenom { denom_info } => {
+            execute::set_burnable_denom(deps, env, info, denom_info)
+        }
+        ExecuteMsg::RemoveBurnableDenom { denom } => {
+            execute::remove_burnable_denom(deps, env, info, denom)
         }
-        ExecuteMsg::SetSwapRouter { router } => execute::set_swap_router(deps, env, info, router),
-        ExecuteMsg::SwapAndBurn { denom, swap_paths } => {
-            execute::swap_and_burn(deps, env, info, denom, swap_paths)
+        ExecuteMsg::SetBurnableToken { token_info } => {
+            execute::set_burnable_token(deps, env, info, token_info)
         }
+        ExecuteMsg::RemoveBurnableToken { token } => {
+            execute::remove_burnable_token(deps, env, info, token)
+        }
+        ExecuteMsg::Burn {
+            referrer,
+            recipient,
+        } => {
+            let recipient = execute::validate_recipient(deps.as_ref(), recipient)?;
+            let recipient = recipient.unwrap_or(info.sender);
+            let referrer = execute::validate_referrer(deps.as_ref(), referrer, &recipient)?;
+            execute::burn(deps, env, recipient, info.funds, None, referrer)
+        }
+        ExecuteMsg::SetSwapRouter { dex, router } => {
+            execute::set_swap_router(deps, env, info, dex, router)
+        }
+        ExecuteMsg::SwapAndBurn {
+            denom,
+            swap_paths,
+            minimum_receive,
+            deadline,
+            referrer,
+            recipient,
+        } => execute::swap_and_burn(
+            deps,
+            env,
+            info,
+            denom,
+            swap_paths,
+            minimum_receive,
+            deadline,
+            referrer,
+            recipient,
+        ),
+        ExecuteMsg::Sweep { denom, recipient } => execute::sweep(deps, env, info, denom, recipient),
         ExecuteMsg::Receive(cw20_msg) => execute::receive_cw20(deps, env, info, cw20_msg),
     }
 }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Owner {} => to_json_binary(&query::owner(deps)?),
         QueryMsg::DevelopmentConfig {} => to_json_binary(&query::development_config(deps)?),
+        QueryMsg::FeeBeneficiaries {} => to_json_binary(&query::fee_beneficiaries(deps)?),
         QueryMsg::RewardWhitelist {} => to_json_binary(&query::reward_whitelist(deps)?),
         QueryMsg::BurnedAmount {} => to_json_binary(&query::burned_amount(deps)?),
+        QueryMsg::BurnedAmounts {} => to_json_binary(&query::burned_amounts(deps)?),
+        QueryMsg::BurnableDenoms {} => to_json_binary(&query::burnable_denoms(deps)?),
+        QueryMsg::BurnableTokens {} => to_json_binary(&query::burnable_tokens(deps)?),
+        QueryMsg::BurnedTokens {} => to_json_binary(&query::burned_tokens(deps)?),
+        QueryMsg::BurnerInfo { address } => to_json_binary(&query::burner_info(deps, address)?),
+        QueryMsg::BurnHistory {
+            start_after,
+            limit,
+            burner,
+        } => to_json_binary(&query::burn_history(deps, start_after, limit, burner)?),
+        QueryMsg::TopBurners { limit, start_after } => {
+            to_json_binary(&query::top_burners(deps, limit, start_after)?)
+        }
         QueryMsg::SwapRouter {} => to_json_binary(&query::swap_router(deps)?),
+        QueryMsg::RewardVesting {} => to_json_binary(&query::reward_vesting(deps)?),
+        QueryMsg::PendingRewards { address } => {
+            to_json_binary(&query::pending_rewards(deps, env, address)?)
+        }
+        QueryMsg::RewardReserves {} => to_json_binary(&query::reward_reserves(deps, env)?),
+        QueryMsg::RewardTiers {} => to_json_binary(&query::reward_tiers(deps)?),
+        QueryMsg::ReferralConfig {} => to_json_binary(&query::referral_config(deps)?),
+        QueryMsg::ReferrerStats { address } => {
+            to_json_binary(&query::referrer_stats(deps, address)?)
+        }
+        QueryMsg::Epoch { id } => to_json_binary(&query::epoch(deps, id)?),
+        QueryMsg::EpochShare { address, id } => {
+            to_json_binary(&query::epoch_share(deps, address, id)?)
+        }
+        QueryMsg::BurnStats { from_day, to_day } => {
+            to_json_binary(&query::burn_stats(deps, from_day, to_day)?)
+        }
+        QueryMsg::SimulateBurn {
+            denom,
+            amount,
+            burner,
+            referrer,
+        } => to_json_binary(&query::simulate_burn(
+            deps, env, denom, amount, burner, referrer,
+        )?),
     }
 }
 
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
-    if msg.id != SWAP_REPLY_ID {
-        return Err(ContractError::InvalidReplyMsg {});
+    let swap_context = SWAP_CONTEXTS
+        .may_load(deps.storage, msg.id)?
+        .ok_or(ContractError::InvalidReplyMsg {})?;
+
+    // a run into an intermediate asset finished, swap its output through the next router
+    if let Some(next_run) = swap_context.pending_runs.first() {
+        let asset_info = next_run.offer_asset_info.clone();
+        let balance = query_asset_balance(&deps.querier, &asset_info, &env.contract.address)?;
+        let received = balance
+            .checked_sub(swap_context.pre_swap_balance.unwrap_or_default())
+            .map_err(StdError::from)?;
+        let amount = match asset_info {
+            AssetInfo::NativeToken { .. } => received - received * LUNC_TAX,
+            AssetInfo::Token { .. } => received,
+        };
+        if amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        return execute::swap_next_run(deps, &env, msg.id, swap_context, amount);
     }
 
-    let burn_amount = deps
-        .querier
-        .query_balance(&env.contract.address, "uluna")
-        .unwrap()
-        .amount;
-    let mut cached_data = CACHE.load(deps.storage)?;
-    if !cached_data.locked {
-        return Err(ContractError::AlreadyUnlocked {});
+    // burn only what the swap produced, stray uluna is left for the owner to sweep
+    let mut burn_coin = deps.querier.query_balance(&env.contract.address, "uluna")?;
+    let pre_swap_balance = swap_context.pre_swap_balance.unwrap_or_default();
+    burn_coin.amount = burn_coin
+        .amount
+        .checked_sub(pre_swap_balance)
+        .map_err(StdError::from)?;
+
+    if let Some(minimum_receive) = swap_context.minimum_receive {
+        if burn_coin.amount < minimum_receive {
+            return Err(ContractError::MinimumReceiveNotMet {
+                received: burn_coin.amount,
+                minimum_receive,
+            });
+        }
     }
 
-    let burner = cached_data.burner.clone();
-    cached_data.locked = false;
-    CACHE.save(deps.storage, &cached_data)?;
+    SWAP_CONTEXTS.remove(deps.storage, msg.id);
+
+    let recipient = swap_context.recipient.unwrap_or(swap_context.burner);
 
-    execute::burn(deps, env.clone(), burner, burn_amount)
+    execute::burn(
+        deps,
+        env.clone(),
+        recipient,
+        vec![burn_coin],
+        swap_context.swap_input,
+        swap_context.referrer,
+    )
+}
+
+// reward balance not yet owed to burners with vesting or unclaimed epoch rewards
+fn available_reward(deps: Deps, asset_info: &AssetInfo, contract: &Addr) -> StdResult<Uint128> {
+    let reward_balance = query_asset_balance(&deps.querier, asset_info, contract)?;
+    Ok(reward_balance.saturating_sub(reserved_reward(deps.storage, &asset_info.to_string())?))
+}
+
+fn reserved_reward(storage: &dyn Storage, key: &str) -> StdResult<Uint128> {
+    let vesting_total = VESTING_REWARD_TOTALS
+        .may_load(storage, key)?
+        .unwrap_or_default();
+    let epoch_total = EPOCH_REWARD_TOTALS
+        .may_load(storage, key)?
+        .unwrap_or_default();
+    Ok(vesting_total + epoch_total)
+}
+
+// development fee of a burn and what is left to burn after the fee and its send tax
+fn split_burn_amount(amount: Uint128, fee_ratio: Decimal) -> (Uint128, Uint128) {
+    let development_fee = amount * fee_ratio.numerator() / fee_ratio.denominator();
+    let send_tax = development_fee * LUNC_TAX;
+    (development_fee, amount - (development_fee + send_tax))
+}
+
+// the referrer's share is only paid when referral payouts are configured
+fn load_referral(
+    storage: &dyn Storage,
+    referrer: Option<&Addr>,
+) -> StdResult<Option<(Addr, ReferralConfig)>> {
+    let referral = match (referrer, REFERRAL_CONFIG.may_load(storage)?) {
+        (Some(referrer), Some(referral_config)) => Some((referrer.clone(), referral_config)),
+        _ => None,
+    };
+    Ok(referral)
+}
+
+// the referrer's cut of `amount` when referral payouts come from `source`
+fn referral_share(
+    referral: Option<&(Addr, ReferralConfig)>,
+    source: ReferralSource,
+    amount: Uint128,
+) -> Uint128 {
+    match referral {
+        Some((_, referral_config)) if referral_config.source == source => {
+            amount * referral_config.share
+        }
+        _ => Uint128::zero(),
+    }
+}
+
+// multiplier of the highest reward tier reached by a burn of burned_luna
+fn reward_multiplier(
+    storage: &dyn Storage,
+    burner: Option<&Addr>,
+    burned_luna: Uint128,
+) -> StdResult<Decimal> {
+    let reward_tiers = match REWARD_TIERS.may_load(storage)? {
+        Some(reward_tiers) => reward_tiers,
+        None => return Ok(Decimal::one()),
+    };
+    let amount = match (reward_tiers.basis, burner) {
+        (RewardTierBasis::Cumulative, Some(burner)) => {
+            let stats = burners().may_load(storage, burner.clone())?;
+            stats.map(|stats| stats.burned_amount).unwrap_or_default() + burned_luna
+        }
+        _ => burned_luna,
+    };
+    let multiplier = reward_tiers
+        .tiers
+        .iter()
+        .rev()
+        .find(|tier| tier.min_amount <= amount)
+        .map_or(Decimal::one(), |tier| tier.multiplier);
+    Ok(multiplier)
 }
 
 mod execute {
@@ -132,6 +340,7 @@ mod execute {
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
+        dex: SwapDex,
         router: String,
     ) -> Result<Response, ContractError> {
         let owner = OWNER.load(deps.storage)?;
@@ -140,14 +349,85 @@ mod execute {
         }
         let router = deps.api.addr_validate(&router)?;
 
-        SWAP_ROUTER.save(deps.storage, &router)?;
+        SWAP_ROUTERS.save(
+            deps.storage,
+            &dex.to_string(),
+            &SwapRouterInfo {
+                dex: dex.clone(),
+                router: router.clone(),
+            },
+        )?;
 
         let res = Response::new()
             .add_attribute("action", "set_swap_router")
+            .add_attribute("dex", dex.to_string())
             .add_attribute("router", router);
         Ok(res)
     }
 
+    pub fn sweep(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        // deposits of the open epoch, rewards owed to burners and what is left of the
+        // deposited reward pool of a whitelisted reward are not stray
+        let key = AssetInfo::NativeToken {
+            denom: denom.clone(),
+        }
+        .to_string();
+        let mut reserved = reserved_reward(deps.storage, &key)?;
+        if REWARD_ASSETS.has(deps.storage, &key) {
+            let deposited = REWARD_DEPOSITS
+                .may_load(deps.storage, &key)?
+                .unwrap_or_default();
+            let paid_out = REWARDS_PAID
+                .may_load(deps.storage, &key)?
+                .unwrap_or_default();
+            reserved += deposited.saturating_sub(paid_out);
+        }
+        if let Some(epoch_id) = LATEST_EPOCH.may_load(deps.storage)? {
+            let epoch = EPOCHS.load(deps.storage, epoch_id)?;
+            if !epoch.processed {
+                let amount_of = |coins: &[Coin]| {
+                    coins
+                        .iter()
+                        .filter(|coin| coin.denom == denom)
+                        .map(|coin| coin.amount)
+                        .sum::<Uint128>()
+                };
+                // the referrers' fee share already left with the deposit
+                reserved += amount_of(&epoch.burned) + amount_of(&epoch.development_fee)
+                    - amount_of(&epoch.referral_fee);
+            }
+        }
+
+        let balance = deps.querier.query_balance(&env.contract.address, &denom)?;
+        let amount = balance.amount.saturating_sub(reserved);
+        if amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        let sweep_coin = Coin { denom, amount };
+
+        let res = Response::new()
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![sweep_coin.clone()],
+            })
+            .add_attribute("action", "sweep")
+            .add_attribute("recipient", recipient)
+            .add_attribute("amount", sweep_coin.to_string());
+        Ok(res)
+    }
+
     pub fn update_development_config(
         deps: DepsMut,
         _env: Env,
@@ -173,7 +453,13 @@ mod execute {
         }
         if let Some(beneficiary) = beneficiary {
             let beneficiary = deps.api.addr_validate(&beneficiary)?;
-            DEVELOPMENT_FEE_BENEFICIARY.save(deps.storage, &beneficiary)?;
+            DEVELOPMENT_FEE_BENEFICIARIES.save(
+                deps.storage,
+                &vec![FeeBeneficiary {
+                    address: beneficiary.clone(),
+                    weight: 1,
+                }],
+            )?;
             attrs.push(Attribute {
                 key: "beneficiary".to_string(),
                 value: beneficiary.to_string(),
@@ -186,6 +472,52 @@ mod execute {
         Ok(res)
     }
 
+    pub fn set_fee_beneficiaries(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        beneficiaries: Vec<FeeBeneficiaryInfo>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        if beneficiaries.is_empty() {
+            return Err(ContractError::InvalidFeeBeneficiaries {});
+        }
+        let mut fee_beneficiaries: Vec<FeeBeneficiary> = vec![];
+        let mut attrs = vec![];
+        for beneficiary in beneficiaries {
+            let address = deps.api.addr_validate(&beneficiary.address)?;
+            if beneficiary.weight == 0
+                || fee_beneficiaries
+                    .iter()
+                    .any(|fee_beneficiary| fee_beneficiary.address == address)
+            {
+                return Err(ContractError::InvalidFeeBeneficiaries {});
+            }
+            attrs.push(Attribute {
+                key: "beneficiary".to_string(),
+                value: address.to_string(),
+            });
+            attrs.push(Attribute {
+                key: "weight".to_string(),
+                value: beneficiary.weight.to_string(),
+            });
+            fee_beneficiaries.push(FeeBeneficiary {
+                address,
+                weight: beneficiary.weight,
+            });
+        }
+        DEVELOPMENT_FEE_BENEFICIARIES.save(deps.storage, &fee_beneficiaries)?;
+
+        let res = Response::new()
+            .add_attribute("action", "set_fee_beneficiaries")
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
     pub fn add_to_reward_whitelist(
         deps: DepsMut,
         _env: Env,
@@ -201,17 +533,25 @@ mod execute {
             return Err(ContractError::ZeroRatio {});
         }
 
-        let token = deps.api.addr_validate(&reward_info.token)?;
-        let existed = REWARD_WHITELIST.has(deps.storage, token.clone());
+        validate_reward_asset(deps.as_ref(), &reward_info.asset_info)?;
+        let key = reward_info.asset_info.to_string();
+        let existed = REWARD_ASSETS.has(deps.storage, &key);
         if existed {
             return Err(ContractError::AlreadyExists {});
         } else {
-            REWARD_WHITELIST.save(deps.storage, token, &reward_info.reward_ratio)?;
+            REWARD_ASSETS.save(
+                deps.storage,
+                &key,
+                &RewardAsset {
+                    asset_info: reward_info.asset_info,
+                    reward_ratio: reward_info.reward_ratio,
+                },
+            )?;
         }
 
         let res = Response::new()
             .add_attribute("action", "add_to_reward_whitelist")
-            .add_attribute("token", reward_info.token)
+            .add_attribute("token", key)
             .add_attribute("reward_ratio", reward_info.reward_ratio.to_string());
         Ok(res)
     }
@@ -220,25 +560,24 @@ mod execute {
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
-        token: String,
+        asset_info: AssetInfo,
     ) -> Result<Response, ContractError> {
         let owner = OWNER.load(deps.storage)?;
         if owner != info.sender {
             return Err(ContractError::Unauthorized {});
         }
 
-        let token = deps.api.addr_validate(&token)?;
-
-        let existed = REWARD_WHITELIST.has(deps.storage, token.clone());
+        let key = asset_info.to_string();
+        let existed = REWARD_ASSETS.has(deps.storage, &key);
         if !existed {
             return Err(ContractError::NotInWhitelist {});
         } else {
-            REWARD_WHITELIST.remove(deps.storage, token.clone());
+            REWARD_ASSETS.remove(deps.storage, &key);
         }
 
         let res = Response::new()
             .add_attribute("action", "remove_from_reward_whitelist")
-            .add_attribute("token", token);
+            .add_attribute("token", key);
         Ok(res)
     }
 
@@ -257,254 +596,1792 @@ mod execute {
             return Err(ContractError::ZeroRatio {});
         }
 
-        let token = deps.api.addr_validate(&reward_info.token)?;
-        let existed = REWARD_WHITELIST.has(deps.storage, token.clone());
+        validate_reward_asset(deps.as_ref(), &reward_info.asset_info)?;
+        let key = reward_info.asset_info.to_string();
+        let existed = REWARD_ASSETS.has(deps.storage, &key);
         if !existed {
             return Err(ContractError::NotInWhitelist {});
         } else {
-            REWARD_WHITELIST.save(deps.storage, token, &reward_info.reward_ratio)?;
+            REWARD_ASSETS.save(
+                deps.storage,
+                &key,
+                &RewardAsset {
+                    asset_info: reward_info.asset_info,
+                    reward_ratio: reward_info.reward_ratio,
+                },
+            )?;
         }
 
         let res = Response::new()
             .add_attribute("action", "update_reward_info")
-            .add_attribute("token", reward_info.token)
+            .add_attribute("token", key)
             .add_attribute("reward_ratio", reward_info.reward_ratio.to_string());
         Ok(res)
     }
 
-    pub fn burn(
+    pub fn update_reward_vesting(
         deps: DepsMut,
-        env: Env,
-        recipient: Addr,
-        burn_amount: Uint128,
+        _env: Env,
+        info: MessageInfo,
+        duration: Option<u64>,
     ) -> Result<Response, ContractError> {
-        if burn_amount.is_zero() {
-            return Err(ContractError::ZeroAmount {});
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
         }
 
-        let fee_beneficiary = DEVELOPMENT_FEE_BENEFICIARY.load(deps.storage)?;
-        let fee_ratio = DEVELOPMENT_FEE_RATIO.load(deps.storage)?;
-        let development_fee = burn_amount * fee_ratio.numerator() / fee_ratio.denominator();
-        let fee_msg = BankMsg::Send {
-            to_address: fee_beneficiary.to_string(),
-            amount: vec![Coin {
-                denom: "uluna".to_string(),
-                amount: development_fee,
-            }],
-        };
-
-        let send_tax = development_fee * LUNC_TAX;
-        let actual_burn_amount = burn_amount - (development_fee + send_tax);
-
-        let rewards = REWARD_WHITELIST
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-            .map(|item| {
-                item.map(|(token, reward_ratio)| {
-                    let reward_amount = actual_burn_amount * reward_ratio;
-                    (token, reward_amount)
-                })
-            })
-            .collect::<StdResult<Vec<_>>>()?;
-
-        let mut reward_msgs = vec![];
-        let mut attrs = vec![];
-        for reward in rewards {
-            let (token, mut reward_amount) = reward;
-            if reward_amount.is_zero() {
-                continue;
-            }
-
-            let cw20_balance_query = WasmQuery::Smart {
-                contract_addr: token.to_string(),
-                msg: to_json_binary(&Cw20QueryMsg::Balance {
-                    address: env.contract.address.to_string(),
-                })
-                .unwrap(),
-            };
-            let reward_token_balance: Cw20BalanceResponse = deps
-                .querier
-                .query(&QueryRequest::Wasm(cw20_balance_query))?;
+        match duration {
+            Some(0) => return Err(ContractError::ZeroVestingDuration {}),
+            Some(duration) => REWARD_VESTING_DURATION.save(deps.storage, &duration)?,
+            None => REWARD_VESTING_DURATION.remove(deps.storage),
+        }
 
-            if reward_token_balance.balance.is_zero() {
-                continue;
-            }
+        let res = Response::new()
+            .add_attribute("action", "update_reward_vesting")
+            .add_attribute(
+                "duration",
+                duration.map_or("none".to_string(), |duration| duration.to_string()),
+            );
+        Ok(res)
+    }
 
-            if reward_amount > reward_token_balance.balance {
-                reward_amount = reward_token_balance.balance;
-            }
+    pub fn set_reward_tiers(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        basis: RewardTierBasis,
+        tiers: Vec<RewardTier>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
 
-            reward_msgs.push(WasmMsg::Execute {
-                contract_addr: token.to_string(),
-                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: recipient.to_string(),
-                    amount: reward_amount,
-                })
-                .unwrap(),
-                funds: vec![],
-            });
+        if tiers.iter().any(|tier| tier.multiplier.is_zero())
+            || tiers
+                .windows(2)
+                .any(|pair| pair[0].min_amount >= pair[1].min_amount)
+        {
+            return Err(ContractError::InvalidRewardTiers {});
+        }
 
+        let mut attrs = vec![];
+        for tier in tiers.iter() {
             attrs.push(Attribute {
-                key: "token".to_string(),
-                value: token.to_string(),
+                key: "min_amount".to_string(),
+                value: tier.min_amount.to_string(),
             });
             attrs.push(Attribute {
-                key: "reward".to_string(),
-                value: reward_amount.to_string(),
+                key: "multiplier".to_string(),
+                value: tier.multiplier.to_string(),
             });
         }
-
-        BURNED_AMOUNT.update(deps.storage, |burned_amount: Uint128| -> StdResult<_> {
-            Ok(burned_amount + actual_burn_amount)
-        })?;
-
-        let burn_msg = BankMsg::Burn {
-            amount: vec![Coin {
-                denom: "uluna".to_string(),
-                amount: actual_burn_amount,
-            }],
-        };
+        if tiers.is_empty() {
+            REWARD_TIERS.remove(deps.storage);
+        } else {
+            REWARD_TIERS.save(deps.storage, &RewardTiers { basis, tiers })?;
+        }
 
         let res = Response::new()
-            .add_message(fee_msg)
-            .add_message(burn_msg)
-            .add_messages(reward_msgs)
-            .add_attribute("action", "burn")
-            .add_attribute("development_fee", development_fee)
-            .add_attribute("burn_amount", actual_burn_amount)
+            .add_attribute("action", "set_reward_tiers")
             .add_attributes(attrs);
         Ok(res)
     }
 
-    pub fn swap_and_burn(
+    pub fn set_referral_config(
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
-        denom: String,
-        swap_paths: Vec<AssetInfo>,
+        referral_config: Option<ReferralConfig>,
     ) -> Result<Response, ContractError> {
-        let funds = info.funds.clone();
-        let mut swap_amount = Uint128::zero();
-        for coin in funds {
-            if coin.denom == denom {
-                swap_amount = coin.amount;
-                break;
-            }
-        }
-        if swap_amount.is_zero() {
-            return Err(ContractError::ZeroAmount {});
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
         }
 
-        let cached_data = CACHE.load(deps.storage)?;
-        if cached_data.locked {
-            return Err(ContractError::Locked {});
+        let mut attrs = vec![];
+        match referral_config {
+            Some(referral_config) => {
+                if referral_config.share.is_zero() || referral_config.share > Decimal::one() {
+                    return Err(ContractError::InvalidReferralShare {});
+                }
+                attrs.push(Attribute {
+                    key: "source".to_string(),
+                    value: match referral_config.source {
+                        ReferralSource::Rewards => "rewards".to_string(),
+                        ReferralSource::DevelopmentFee => "development_fee".to_string(),
+                    },
+                });
+                attrs.push(Attribute {
+                    key: "share".to_string(),
+                    value: referral_config.share.to_string(),
+                });
+                REFERRAL_CONFIG.save(deps.storage, &referral_config)?;
+            }
+            None => REFERRAL_CONFIG.remove(deps.storage),
         }
-        CACHE.save(
-            deps.storage,
-            &CachedData {
-                locked: true,
-                burner: info.sender,
-            },
-        )?;
 
-        let offer_asset = AssetInfo::NativeToken {
-            denom: denom.clone(),
-        };
-        let ask_asset = AssetInfo::NativeToken {
-            denom: "uluna".to_string(),
-        };
-        let swap_operations = create_swap_operations(offer_asset, ask_asset, swap_paths);
-        let swap_router = SWAP_ROUTER.load(deps.storage)?;
-        let tax = swap_amount * LUNC_TAX;
-        let actual_swap_amount = swap_amount - tax;
+        let res = Response::new()
+            .add_attribute("action", "set_referral_config")
+            .add_attributes(attrs);
+        Ok(res)
+    }
 
-        let swap_wasm_msg = WasmMsg::Execute {
-            contract_addr: swap_router.to_string(),
-            msg: to_json_binary(&swap_operations).unwrap(),
-            funds: vec![Coin {
-                denom,
-                amount: actual_swap_amount,
-            }],
-        };
-        let swap_submsg = SubMsg::reply_on_success(swap_wasm_msg, SWAP_REPLY_ID);
-        Ok(Response::new().add_submessage(swap_submsg))
+    // the referrer is compared against whoever receives the burn rewards
+    pub fn validate_referrer(
+        deps: Deps,
+        referrer: Option<String>,
+        burner: &Addr,
+    ) -> Result<Option<Addr>, ContractError> {
+        let referrer = referrer
+            .map(|referrer| deps.api.addr_validate(&referrer))
+            .transpose()?;
+        if referrer.as_ref() == Some(burner) {
+            return Err(ContractError::SelfReferral {});
+        }
+        Ok(referrer)
     }
 
-    pub fn receive_cw20(
+    pub fn set_epoch_duration(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
-        cw20_msg: Cw20ReceiveMsg,
+        duration: Option<u64>,
     ) -> Result<Response, ContractError> {
-        let token_in = info.sender;
-        let amount = cw20_msg.amount;
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
 
-        let burner = deps.api.addr_validate(&cw20_msg.sender)?;
-        let cached_data = CACHE.load(deps.storage)?;
-        if cached_data.locked {
-            return Err(ContractError::Locked {});
+        // an epoch left open when epochs are disabled can still be processed
+        let mut attrs = vec![];
+        match duration {
+            Some(0) => return Err(ContractError::ZeroEpochDuration {}),
+            Some(duration) => {
+                EPOCH_DURATION.save(deps.storage, &duration)?;
+                let epoch_id = open_epoch(deps.storage, env.block.time.seconds())?;
+                attrs.push(Attribute {
+                    key: "epoch".to_string(),
+                    value: epoch_id.to_string(),
+                });
+            }
+            None => EPOCH_DURATION.remove(deps.storage),
         }
-        CACHE.save(
-            deps.storage,
-            &CachedData {
-                locked: true,
-                burner,
+
+        let res = Response::new()
+            .add_attribute("action", "set_epoch_duration")
+            .add_attribute(
+                "duration",
+                duration.map_or("none".to_string(), |duration| duration.to_string()),
+            )
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    // opens the next epoch unless the latest one is still open
+    fn open_epoch(storage: &mut dyn Storage, now: u64) -> StdResult<u64> {
+        let latest_epoch = LATEST_EPOCH.may_load(storage)?;
+        if let Some(epoch_id) = latest_epoch {
+            if !EPOCHS.load(storage, epoch_id)?.processed {
+                return Ok(epoch_id);
+            }
+        }
+
+        let epoch_id = latest_epoch.unwrap_or_default() + 1;
+        LATEST_EPOCH.save(storage, &epoch_id)?;
+        EPOCHS.save(
+            storage,
+            epoch_id,
+            &Epoch {
+                id: epoch_id,
+                start_time: now,
+                burned: vec![],
+                development_fee: vec![],
+                referral_fee: vec![],
+                total_weight: Uint128::zero(),
+                depositor_count: 0,
+                processed: false,
+                rewards: vec![],
+                unclaimed_weight: Uint128::zero(),
+                unclaimed_rewards: vec![],
             },
         )?;
+        Ok(epoch_id)
+    }
 
-        match from_json(&cw20_msg.msg) {
-            Ok(Cw20HookMsg::SwapAndBurn { swap_paths }) => {
-                let offer_asset = AssetInfo::Token {
-                    contract_addr: token_in.to_string(),
-                };
-                let ask_asset = AssetInfo::NativeToken {
-                    denom: "uluna".to_string(),
-                };
-                let swap_operations = create_swap_operations(offer_asset, ask_asset, swap_paths);
-                let swap_router = SWAP_ROUTER.load(deps.storage)?;
+    // epoch deposits get no reward tier multiplier, the referrer's fee share is paid now and
+    // its reward share, like the depositor's rewards and burn record, when rewards are claimed
+    fn deposit_to_epoch(
+        deps: DepsMut,
+        recipient: Addr,
+        burn_coins: Vec<Coin>,
+        referrer: Option<Addr>,
+    ) -> Result<Response, ContractError> {
+        let epoch_id = LATEST_EPOCH.load(deps.storage)?;
+        let mut epoch = EPOCHS.load(deps.storage, epoch_id)?;
+        if epoch.processed {
+            return Err(ContractError::NoOpenEpoch {});
+        }
+        let mut deposit = match EPOCH_DEPOSITS.may_load(deps.storage, (&recipient, epoch_id))? {
+            Some(deposit) => deposit,
+            None => {
+                epoch.depositor_count += 1;
+                EpochDeposit {
+                    burned: vec![],
+                    development_fee: vec![],
+                    weight: Uint128::zero(),
+                }
+            }
+        };
 
-                let cw20_send_msg = WasmMsg::Execute {
-                    contract_addr: token_in.to_string(),
-                    msg: to_json_binary(&Cw20ExecuteMsg::Send {
-                        contract: swap_router.to_string(),
-                        amount,
-                        msg: to_json_binary(&swap_operations).unwrap(),
-                    })?,
-                    funds: vec![],
-                };
-                let cw20_send_submsg = SubMsg::reply_on_success(cw20_send_msg, SWAP_REPLY_ID);
+        let default_fee_ratio = DEVELOPMENT_FEE_RATIO.load(deps.storage)?;
+        let referral = load_referral(deps.storage, referrer.as_ref())?;
+        let mut burned_luna = Uint128::zero();
+        let mut referral_fee_coins = vec![];
+        let mut weight = Uint128::zero();
+        for burn_coin in burn_coins {
+            let denom = burn_coin.denom;
+            let burnable_denom =
+                BURNABLE_DENOMS
+                    .may_load(deps.storage, &denom)?
+                    .ok_or_else(|| ContractError::NotBurnableDenom {
+                        denom: denom.clone(),
+                    })?;
 
-                Ok(Response::new().add_submessage(cw20_send_submsg))
+            let fee_ratio = burnable_denom.fee_ratio.unwrap_or(default_fee_ratio);
+            let (development_fee, actual_burn_amount) =
+                split_burn_amount(burn_coin.amount, fee_ratio);
+            let referral_fee = referral_share(
+                referral.as_ref(),
+                ReferralSource::DevelopmentFee,
+                development_fee,
+            );
+            weight += actual_burn_amount * burnable_denom.reward_weight;
+            add_coin(&mut epoch.burned, &denom, actual_burn_amount);
+            add_coin(&mut epoch.development_fee, &denom, development_fee);
+            add_coin(&mut epoch.referral_fee, &denom, referral_fee);
+            add_coin(&mut referral_fee_coins, &denom, referral_fee);
+            add_coin(&mut deposit.burned, &denom, actual_burn_amount);
+            add_coin(&mut deposit.development_fee, &denom, development_fee);
+            if denom == "uluna" {
+                burned_luna += actual_burn_amount;
             }
-            Err(err) => Err(ContractError::StdError(err)),
         }
-    }
-}
+        deposit.weight += weight;
+        epoch.total_weight += weight;
+        epoch.unclaimed_weight += weight;
+        EPOCH_DEPOSITS.save(deps.storage, (&recipient, epoch_id), &deposit)?;
+        EPOCHS.save(deps.storage, epoch_id, &epoch)?;
 
-mod query {
-    use super::*;
+        let mut res = Response::new();
+        if let Some(referrer) = &referrer {
+            EPOCH_REFERRALS.update(
+                deps.storage,
+                (&recipient, epoch_id, referrer),
+                |referred_weight: Option<Uint128>| -> StdResult<_> {
+                    Ok(referred_weight.unwrap_or_default() + weight)
+                },
+            )?;
+            let referral_earnings = referral_fee_coins
+                .iter()
+                .map(|fee_coin| BurnerReward {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: fee_coin.denom.clone(),
+                    },
+                    amount: fee_coin.amount,
+                })
+                .collect::<Vec<_>>();
+            record_referrer(deps.storage, referrer, burned_luna, &referral_earnings)?;
+            if !referral_fee_coins.is_empty() {
+                res = res.add_message(BankMsg::Send {
+                    to_address: referrer.to_string(),
+                    amount: referral_fee_coins,
+                });
+            }
+            res = res.add_attribute("referrer", referrer.to_string());
+        }
 
-    pub fn owner(deps: Deps) -> StdResult<OwnerResponse> {
-        let owner = OWNER.load(deps.storage)?;
-        Ok(OwnerResponse { owner })
+        let res = res
+            .add_attribute("action", "deposit_to_epoch")
+            .add_attribute("epoch", epoch_id.to_string())
+            .add_attribute("recipient", recipient)
+            .add_attribute("weight", weight);
+        Ok(res)
     }
 
-    pub fn development_config(deps: Deps) -> StdResult<DevelopmentConfigResponse> {
+    fn add_coin(coins: &mut Vec<Coin>, denom: &str, amount: Uint128) {
+        if amount.is_zero() {
+            return;
+        }
+        match coins.iter_mut().find(|coin| coin.denom == denom) {
+            Some(coin) => coin.amount += amount,
+            None => coins.push(Coin {
+                denom: denom.to_string(),
+                amount,
+            }),
+        }
+    }
+
+    pub fn process_epoch(
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let now = env.block.time.seconds();
+        let epoch_id = LATEST_EPOCH
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoOpenEpoch {})?;
+        let mut epoch = EPOCHS.load(deps.storage, epoch_id)?;
+        if epoch.processed {
+            return Err(ContractError::NoOpenEpoch {});
+        }
+        let epoch_duration = EPOCH_DURATION.may_load(deps.storage)?;
+        if let Some(duration) = epoch_duration {
+            let end_time = epoch.start_time + duration;
+            if now < end_time {
+                return Err(ContractError::EpochNotEnded { end_time });
+            }
+        }
+
+        let reward_whitelist = REWARD_ASSETS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, reward_asset)| reward_asset))
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut rewards = vec![];
+        let mut attrs = vec![];
+        for reward_asset in reward_whitelist {
+            let mut reward_amount = epoch.total_weight * reward_asset.reward_ratio;
+            if reward_amount.is_zero() {
+                continue;
+            }
+            let key = reward_asset.asset_info.to_string();
+
+            let available = available_reward(
+                deps.as_ref(),
+                &reward_asset.asset_info,
+                &env.contract.address,
+            )?;
+            if reward_amount >= available {
+                reward_amount = available;
+                attrs.push(Attribute {
+                    key: "reward_depleted".to_string(),
+                    value: key.clone(),
+                });
+            }
+            if reward_amount.is_zero() {
+                continue;
+            }
+
+            EPOCH_REWARD_TOTALS.update(
+                deps.storage,
+                &key,
+                |total: Option<Uint128>| -> StdResult<_> {
+                    Ok(total.unwrap_or_default() + reward_amount)
+                },
+            )?;
+            REWARDS_PAID.update(
+                deps.storage,
+                &key,
+                |paid: Option<Uint128>| -> StdResult<_> {
+                    Ok(paid.unwrap_or_default() + reward_amount)
+                },
+            )?;
+            attrs.push(Attribute {
+                key: "token".to_string(),
+                value: key,
+            });
+            attrs.push(Attribute {
+                key: "reward".to_string(),
+                value: reward_amount.to_string(),
+            });
+            rewards.push(BurnerReward {
+                asset_info: reward_asset.asset_info,
+                amount: reward_amount,
+            });
+        }
+
+        for burned in epoch.burned.iter() {
+            BURNED_AMOUNTS.update(
+                deps.storage,
+                &burned.denom,
+                |burned_amount: Option<Uint128>| -> StdResult<_> {
+                    Ok(burned_amount.unwrap_or_default() + burned.amount)
+                },
+            )?;
+        }
+        // the deposits are burned now, their depositors are counted when they claim
+        let burned_luna = epoch
+            .burned
+            .iter()
+            .filter(|coin| coin.denom == "uluna")
+            .map(|coin| coin.amount)
+            .sum::<Uint128>();
+        record_burn_stats(
+            deps.storage,
+            now,
+            None,
+            burned_luna,
+            &epoch.development_fee,
+            &rewards,
+            None,
+        )?;
+        epoch.processed = true;
+        epoch.rewards = rewards.clone();
+        epoch.unclaimed_rewards = rewards;
+        EPOCHS.save(deps.storage, epoch_id, &epoch)?;
+        if epoch_duration.is_some() {
+            open_epoch(deps.storage, now)?;
+        }
+
+        let fee_beneficiaries = DEVELOPMENT_FEE_BENEFICIARIES.load(deps.storage)?;
+        let beneficiary_fee_coins = epoch
+            .development_fee
+            .into_iter()
+            .map(|mut fee_coin| {
+                if let Some(referral_fee) = epoch
+                    .referral_fee
+                    .iter()
+                    .find(|referral_fee| referral_fee.denom == fee_coin.denom)
+                {
+                    fee_coin.amount -= referral_fee.amount;
+                }
+                fee_coin
+            })
+            .collect();
+        let mut res = Response::new().add_messages(split_development_fee(
+            &fee_beneficiaries,
+            beneficiary_fee_coins,
+        ));
+        if !epoch.burned.is_empty() {
+            res = res.add_message(BankMsg::Burn {
+                amount: epoch.burned,
+            });
+        }
+
+        let res = res
+            .add_attribute("action", "process_epoch")
+            .add_attribute("epoch", epoch_id.to_string())
+            .add_attribute("depositor_count", epoch.depositor_count.to_string())
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    pub fn claim_epoch_rewards(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let deposits = EPOCH_DEPOSITS
+            .prefix(&info.sender)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let now = env.block.time.seconds();
+        let mut claimed_rewards = vec![];
+        let mut referral_msgs = vec![];
+        let mut attrs = vec![];
+        for (epoch_id, deposit) in deposits {
+            let mut epoch = EPOCHS.load(deps.storage, epoch_id)?;
+            if !epoch.processed {
+                continue;
+            }
+
+            let rewards = epoch.reward_share(deposit.weight);
+            for reward in rewards.iter() {
+                if let Some(unclaimed) = epoch
+                    .unclaimed_rewards
+                    .iter_mut()
+                    .find(|unclaimed| unclaimed.asset_info == reward.asset_info)
+                {
+                    unclaimed.amount -= reward.amount;
+                }
+            }
+            epoch.unclaimed_weight -= deposit.weight;
+            EPOCHS.save(deps.storage, epoch_id, &epoch)?;
+            EPOCH_DEPOSITS.remove(deps.storage, (&info.sender, epoch_id));
+            for reward in rewards.iter() {
+                EPOCH_REWARD_TOTALS.update(
+                    deps.storage,
+                    &reward.asset_info.to_string(),
+                    |total: Option<Uint128>| -> StdResult<_> {
+                        Ok(total.unwrap_or_default().checked_sub(reward.amount)?)
+                    },
+                )?;
+            }
+
+            // referrers take their share of the rewards of the weight they referred
+            let referrals = EPOCH_REFERRALS
+                .prefix((&info.sender, epoch_id))
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            let mut rewards = rewards;
+            for (referrer, referred_weight) in referrals.iter() {
+                EPOCH_REFERRALS.remove(deps.storage, (&info.sender, epoch_id, referrer));
+                let referral = load_referral(deps.storage, Some(referrer))?;
+                let mut referral_earnings = vec![];
+                for reward in rewards.iter_mut() {
+                    let referral_reward = referral_share(
+                        referral.as_ref(),
+                        ReferralSource::Rewards,
+                        reward
+                            .amount
+                            .multiply_ratio(*referred_weight, deposit.weight),
+                    );
+                    if referral_reward.is_zero() {
+                        continue;
+                    }
+                    reward.amount -= referral_reward;
+                    referral_msgs.push(create_transfer_msg(
+                        &reward.asset_info,
+                        referrer,
+                        referral_reward,
+                    )?);
+                    referral_earnings.push(BurnerReward {
+                        asset_info: reward.asset_info.clone(),
+                        amount: referral_reward,
+                    });
+                }
+                add_referral_earnings(deps.storage, referrer, &referral_earnings)?;
+            }
+
+            // the deposit was burned when its epoch was processed
+            let burned_luna = deposit
+                .burned
+                .iter()
+                .filter(|coin| coin.denom == "uluna")
+                .map(|coin| coin.amount)
+                .sum::<Uint128>();
+            record_burner(deps.storage, &info.sender, burned_luna, &rewards)?;
+            record_burn_stats(
+                deps.storage,
+                now,
+                Some(&info.sender),
+                Uint128::zero(),
+                &[],
+                &[],
+                None,
+            )?;
+            let record_id = next_burn_record_id(deps.storage)?;
+            let record = BurnRecord {
+                id: record_id,
+                burner: info.sender.clone(),
+                swap_input: None,
+                referrer: referrals.first().map(|(referrer, _)| referrer.clone()),
+                token_burn: None,
+                burned: deposit.burned,
+                development_fee: deposit.development_fee,
+                rewards: rewards.clone(),
+                time: now,
+            };
+            burn_records().save(deps.storage, record_id, &record)?;
+            add_rewards(&mut claimed_rewards, &rewards);
+            attrs.push(Attribute {
+                key: "epoch".to_string(),
+                value: epoch_id.to_string(),
+            });
+        }
+        if attrs.is_empty() {
+            return Err(ContractError::ZeroAmount {});
+        }
+
+        let mut claim_msgs = vec![];
+        for reward in claimed_rewards.iter() {
+            if reward.amount.is_zero() {
+                continue;
+            }
+            let key = reward.asset_info.to_string();
+            claim_msgs.push(create_transfer_msg(
+                &reward.asset_info,
+                &info.sender,
+                reward.amount,
+            )?);
+            attrs.push(Attribute {
+                key: "token".to_string(),
+                value: key,
+            });
+            attrs.push(Attribute {
+                key: "claimed".to_string(),
+                value: reward.amount.to_string(),
+            });
+        }
+
+        let res = Response::new()
+            .add_messages(claim_msgs)
+            .add_messages(referral_msgs)
+            .add_attribute("action", "claim_epoch_rewards")
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    pub fn claim_rewards(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let now = env.block.time.seconds();
+        let vestings = REWARD_VESTINGS
+            .prefix(&info.sender)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut claim_msgs = vec![];
+        let mut attrs = vec![];
+        for (key, mut vesting) in vestings {
+            vesting.settle(now);
+            let claimed = vesting.claimable;
+            vesting.claimable = Uint128::zero();
+            if vesting.locked.is_zero() {
+                REWARD_VESTINGS.remove(deps.storage, (&info.sender, &key));
+            } else {
+                REWARD_VESTINGS.save(deps.storage, (&info.sender, &key), &vesting)?;
+            }
+            if claimed.is_zero() {
+                continue;
+            }
+
+            VESTING_REWARD_TOTALS.update(
+                deps.storage,
+                &key,
+                |total: Option<Uint128>| -> StdResult<_> {
+                    Ok(total.unwrap_or_default().checked_sub(claimed)?)
+                },
+            )?;
+            claim_msgs.push(create_transfer_msg(
+                &vesting.asset_info,
+                &info.sender,
+                claimed,
+            )?);
+            attrs.push(Attribute {
+                key: "token".to_string(),
+                value: key,
+            });
+            attrs.push(Attribute {
+                key: "claimed".to_string(),
+                value: claimed.to_string(),
+            });
+        }
+        if claim_msgs.is_empty() {
+            return Err(ContractError::ZeroAmount {});
+        }
+
+        let res = Response::new()
+            .add_messages(claim_msgs)
+            .add_attribute("action", "claim_rewards")
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    pub fn deposit_native_rewards(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let funds = info
+            .funds
+            .into_iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .collect::<Vec<_>>();
+        if funds.is_empty() {
+            return Err(ContractError::ZeroAmount {});
+        }
+
+        let mut attrs = vec![];
+        for coin in funds {
+            if !REWARD_ASSETS.has(deps.storage, &coin.denom) {
+                return Err(ContractError::NotInWhitelist {});
+            }
+            let deposited = REWARD_DEPOSITS.update(
+                deps.storage,
+                &coin.denom,
+                |deposited: Option<Uint128>| -> StdResult<_> {
+                    Ok(deposited.unwrap_or_default() + coin.amount)
+                },
+            )?;
+            attrs.push(Attribute {
+                key: "token".to_string(),
+                value: coin.denom,
+            });
+            attrs.push(Attribute {
+                key: "amount".to_string(),
+                value: coin.amount.to_string(),
+            });
+            attrs.push(Attribute {
+                key: "deposited".to_string(),
+                value: deposited.to_string(),
+            });
+        }
+
+        let res = Response::new()
+            .add_attribute("action", "deposit_rewards")
+            .add_attribute("depositor", info.sender)
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    // rewards are paid from the contract balance, so they cannot be burnable denoms or tokens
+    fn validate_reward_asset(deps: Deps, asset_info: &AssetInfo) -> Result<(), ContractError> {
+        match asset_info {
+            AssetInfo::Token { contract_addr } => {
+                let token = deps.api.addr_validate(contract_addr)?;
+                if BURNABLE_TOKENS.has(deps.storage, &token) {
+                    return Err(ContractError::RewardTokenBurnable {
+                        token: token.to_string(),
+                    });
+                }
+            }
+            AssetInfo::NativeToken { denom } => {
+                if BURNABLE_DENOMS.has(deps.storage, denom) {
+                    return Err(ContractError::RewardDenomBurnable {
+                        denom: denom.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_burnable_denom(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        denom_info: BurnableDenomInfo,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        if let Some(fee_ratio) = denom_info.fee_ratio {
+            if fee_ratio >= Decimal::one() {
+                return Err(ContractError::FeeRatioMustBeLessThanOne {});
+            }
+        }
+        if REWARD_ASSETS.has(deps.storage, &denom_info.denom) {
+            return Err(ContractError::RewardDenomBurnable {
+                denom: denom_info.denom,
+            });
+        }
+        BURNABLE_DENOMS.save(
+            deps.storage,
+            &denom_info.denom,
+            &BurnableDenom {
+                fee_ratio: denom_info.fee_ratio,
+                reward_weight: denom_info.reward_weight,
+            },
+        )?;
+
+        let fee_ratio = denom_info
+            .fee_ratio
+            .map_or("default".to_string(), |fee_ratio| fee_ratio.to_string());
+        let res = Response::new()
+            .add_attribute("action", "set_burnable_denom")
+            .add_attribute("denom", denom_info.denom)
+            .add_attribute("fee_ratio", fee_ratio)
+            .add_attribute("reward_weight", denom_info.reward_weight.to_string());
+        Ok(res)
+    }
+
+    pub fn remove_burnable_denom(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        denom: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        // swaps always end in uluna, which is burned in reply
+        if denom == "uluna" {
+            return Err(ContractError::SwapDenomRequired {});
+        }
+        if !BURNABLE_DENOMS.has(deps.storage, &denom) {
+            return Err(ContractError::NotBurnableDenom { denom });
+        }
+        BURNABLE_DENOMS.remove(deps.storage, &denom);
+
+        let res = Response::new()
+            .add_attribute("action", "remove_burnable_denom")
+            .add_attribute("denom", denom);
+        Ok(res)
+    }
+
+    pub fn set_burnable_token(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_info: BurnableTokenInfo,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        if let Some(fee_ratio) = token_info.fee_ratio {
+            if fee_ratio >= Decimal::one() {
+                return Err(ContractError::FeeRatioMustBeLessThanOne {});
+            }
+        }
+        let token = deps.api.addr_validate(&token_info.token)?;
+        if REWARD_ASSETS.has(deps.storage, token.as_str()) {
+            return Err(ContractError::RewardTokenBurnable {
+                token: token.to_string(),
+            });
+        }
+        BURNABLE_TOKENS.save(
+            deps.storage,
+            &token,
+            &BurnableDenom {
+                fee_ratio: token_info.fee_ratio,
+                reward_weight: token_info.reward_weight,
+            },
+        )?;
+
+        let fee_ratio = token_info
+            .fee_ratio
+            .map_or("default".to_string(), |fee_ratio| fee_ratio.to_string());
+        let res = Response::new()
+            .add_attribute("action", "set_burnable_token")
+            .add_attribute("token", token)
+            .add_attribute("fee_ratio", fee_ratio)
+            .add_attribute("reward_weight", token_info.reward_weight.to_string());
+        Ok(res)
+    }
+
+    pub fn remove_burnable_token(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let token = deps.api.addr_validate(&token)?;
+        if !BURNABLE_TOKENS.has(deps.storage, &token) {
+            return Err(ContractError::NotBurnableToken {
+                token: token.to_string(),
+            });
+        }
+        BURNABLE_TOKENS.remove(deps.storage, &token);
+
+        let res = Response::new()
+            .add_attribute("action", "remove_burnable_token")
+            .add_attribute("token", token);
+        Ok(res)
+    }
+
+    pub fn validate_recipient(
+        deps: Deps,
+        recipient: Option<String>,
+    ) -> Result<Option<Addr>, ContractError> {
+        let recipient = recipient
+            .map(|recipient| deps.api.addr_validate(&recipient))
+            .transpose()?;
+        Ok(recipient)
+    }
+
+    pub fn burn(
+        mut deps: DepsMut,
+        env: Env,
+        recipient: Addr,
+        burn_coins: Vec<Coin>,
+        swap_input: Option<SwapInput>,
+        referrer: Option<Addr>,
+    ) -> Result<Response, ContractError> {
+        let burn_coins = burn_coins
+            .into_iter()
+            .filter(|burn_coin| !burn_coin.amount.is_zero())
+            .collect::<Vec<_>>();
+        if burn_coins.is_empty() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        if EPOCH_DURATION.exists(deps.storage) {
+            return deposit_to_epoch(deps, recipient, burn_coins, referrer);
+        }
+
+        let fee_beneficiaries = DEVELOPMENT_FEE_BENEFICIARIES.load(deps.storage)?;
+        let default_fee_ratio = DEVELOPMENT_FEE_RATIO.load(deps.storage)?;
+        let reward_whitelist = REWARD_ASSETS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, reward_asset)| reward_asset))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut rewards = reward_whitelist
+            .iter()
+            .map(|reward_asset| (reward_asset.asset_info.clone(), Uint128::zero()))
+            .collect::<Vec<_>>();
+        let mut fee_coins = vec![];
+        let mut actual_burn_coins = vec![];
+        let mut burned_luna = Uint128::zero();
+        let mut attrs = vec![];
+        for burn_coin in burn_coins {
+            let denom = burn_coin.denom;
+            let burnable_denom =
+                BURNABLE_DENOMS
+                    .may_load(deps.storage, &denom)?
+                    .ok_or_else(|| ContractError::NotBurnableDenom {
+                        denom: denom.clone(),
+                    })?;
+
+            let fee_ratio = burnable_denom.fee_ratio.unwrap_or(default_fee_ratio);
+            let (development_fee, actual_burn_amount) =
+                split_burn_amount(burn_coin.amount, fee_ratio);
+
+            for (reward, reward_asset) in rewards.iter_mut().zip(reward_whitelist.iter()) {
+                reward.1 +=
+                    actual_burn_amount * reward_asset.reward_ratio * burnable_denom.reward_weight;
+            }
+
+            BURNED_AMOUNTS.update(
+                deps.storage,
+                &denom,
+                |burned_amount: Option<Uint128>| -> StdResult<_> {
+                    Ok(burned_amount.unwrap_or_default() + actual_burn_amount)
+                },
+            )?;
+
+            attrs.push(Attribute {
+                key: "denom".to_string(),
+                value: denom.clone(),
+            });
+            attrs.push(Attribute {
+                key: "development_fee".to_string(),
+                value: development_fee.to_string(),
+            });
+            attrs.push(Attribute {
+                key: "burn_amount".to_string(),
+                value: actual_burn_amount.to_string(),
+            });
+
+            if !development_fee.is_zero() {
+                fee_coins.push(Coin {
+                    denom: denom.clone(),
+                    amount: development_fee,
+                });
+            }
+            if denom == "uluna" {
+                burned_luna += actual_burn_amount;
+            }
+            if !actual_burn_amount.is_zero() {
+                actual_burn_coins.push(Coin {
+                    denom,
+                    amount: actual_burn_amount,
+                });
+            }
+        }
+
+        let referral = load_referral(deps.storage, referrer.as_ref())?;
+        let mut referral_earnings = vec![];
+        let mut referral_fee_msg = None;
+        let mut beneficiary_fee_coins = fee_coins.clone();
+        if let Some((referrer, _)) = &referral {
+            let mut referral_fee_coins = vec![];
+            for fee_coin in beneficiary_fee_coins.iter_mut() {
+                let referral_fee = referral_share(
+                    referral.as_ref(),
+                    ReferralSource::DevelopmentFee,
+                    fee_coin.amount,
+                );
+                if referral_fee.is_zero() {
+                    continue;
+                }
+                fee_coin.amount -= referral_fee;
+                referral_fee_coins.push(Coin {
+                    denom: fee_coin.denom.clone(),
+                    amount: referral_fee,
+                });
+                referral_earnings.push(BurnerReward {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: fee_coin.denom.clone(),
+                    },
+                    amount: referral_fee,
+                });
+            }
+            if !referral_fee_coins.is_empty() {
+                referral_fee_msg = Some(BankMsg::Send {
+                    to_address: referrer.to_string(),
+                    amount: referral_fee_coins,
+                });
+            }
+        }
+
+        let multiplier = reward_multiplier(deps.storage, Some(&recipient), burned_luna)?;
+        if multiplier != Decimal::one() {
+            attrs.push(Attribute {
+                key: "reward_multiplier".to_string(),
+                value: multiplier.to_string(),
+            });
+        }
+        let rewards = rewards
+            .into_iter()
+            .map(|(asset_info, reward_amount)| (asset_info, reward_amount * multiplier))
+            .collect();
+        let (reward_msgs, paid_rewards) = distribute_rewards(
+            deps.branch(),
+            &env,
+            &recipient,
+            rewards,
+            referral.as_ref(),
+            &mut attrs,
+            &mut referral_earnings,
+        )?;
+
+        record_burner(deps.storage, &recipient, burned_luna, &paid_rewards)?;
+        record_burn_stats(
+            deps.storage,
+            env.block.time.seconds(),
+            Some(&recipient),
+            burned_luna,
+            &fee_coins,
+            &paid_rewards,
+            None,
+        )?;
+        if let Some(referrer) = &referrer {
+            record_referrer(deps.storage, referrer, burned_luna, &referral_earnings)?;
+            attrs.push(Attribute {
+                key: "referrer".to_string(),
+                value: referrer.to_string(),
+            });
+        }
+        let record_id = next_burn_record_id(deps.storage)?;
+        let record = BurnRecord {
+            id: record_id,
+            burner: recipient,
+            swap_input,
+            referrer: referrer.clone(),
+            token_burn: None,
+            burned: actual_burn_coins.clone(),
+            development_fee: fee_coins.clone(),
+            rewards: paid_rewards,
+            time: env.block.time.seconds(),
+        };
+        burn_records().save(deps.storage, record_id, &record)?;
+
+        let mut res = Response::new()
+            .add_messages(split_development_fee(
+                &fee_beneficiaries,
+                beneficiary_fee_coins,
+            ))
+            .add_messages(referral_fee_msg);
+        if !actual_burn_coins.is_empty() {
+            res = res.add_message(BankMsg::Burn {
+                amount: actual_burn_coins,
+            });
+        }
+
+        let res = res
+            .add_messages(reward_msgs)
+            .add_attribute("action", "burn")
+            .add_attribute("record_id", record_id.to_string())
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    // cw20 transfers pay no send tax, so all but the development fee is burned
+    fn burn_token(
+        mut deps: DepsMut,
+        env: Env,
+        token: Addr,
+        recipient: Addr,
+        amount: Uint128,
+        referrer: Option<Addr>,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        let burnable_token = BURNABLE_TOKENS
+            .may_load(deps.storage, &token)?
+            .ok_or_else(|| ContractError::NotBurnableToken {
+                token: token.to_string(),
+            })?;
+
+        let fee_ratio = match burnable_token.fee_ratio {
+            Some(fee_ratio) => fee_ratio,
+            None => DEVELOPMENT_FEE_RATIO.load(deps.storage)?,
+        };
+        let development_fee = amount * fee_ratio;
+        let actual_burn_amount = amount - development_fee;
+        let token_asset = AssetInfo::Token {
+            contract_addr: token.to_string(),
+        };
+
+        let referral = load_referral(deps.storage, referrer.as_ref())?;
+        let mut referral_earnings = vec![];
+        let mut referral_fee = Uint128::zero();
+        let mut referral_fee_msgs = vec![];
+        if let Some((referrer, _)) = &referral {
+            referral_fee = referral_share(
+                referral.as_ref(),
+                ReferralSource::DevelopmentFee,
+                development_fee,
+            );
+            if !referral_fee.is_zero() {
+                referral_fee_msgs.push(create_transfer_msg(&token_asset, referrer, referral_fee)?);
+                referral_earnings.push(BurnerReward {
+                    asset_info: token_asset.clone(),
+                    amount: referral_fee,
+                });
+            }
+        }
+        let fee_beneficiaries = DEVELOPMENT_FEE_BENEFICIARIES.load(deps.storage)?;
+        let fee_msgs = split_token_fee(
+            &fee_beneficiaries,
+            &token_asset,
+            development_fee - referral_fee,
+        )?;
+
+        BURNED_TOKENS.update(
+            deps.storage,
+            &token,
+            |burned_amount: Option<Uint128>| -> StdResult<_> {
+                Ok(burned_amount.unwrap_or_default() + actual_burn_amount)
+            },
+        )?;
+
+        let mut attrs = vec![];
+        // reward tiers are measured in uluna, so only cumulative tiers can apply
+        let multiplier = reward_multiplier(deps.storage, Some(&recipient), Uint128::zero())?;
+        if multiplier != Decimal::one() {
+            attrs.push(Attribute {
+                key: "reward_multiplier".to_string(),
+                value: multiplier.to_string(),
+            });
+        }
+        let rewards = REWARD_ASSETS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(_, reward_asset)| {
+                    let reward_amount = actual_burn_amount
+                        * reward_asset.reward_ratio
+                        * burnable_token.reward_weight
+                        * multiplier;
+                    (reward_asset.asset_info, reward_amount)
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let (reward_msgs, paid_rewards) = distribute_rewards(
+            deps.branch(),
+            &env,
+            &recipient,
+            rewards,
+            referral.as_ref(),
+            &mut attrs,
+            &mut referral_earnings,
+        )?;
+
+        // the leaderboard and burned_luna only count uluna, the token amounts go to token_burns
+        let token_burn = TokenBurn {
+            token: token.clone(),
+            burned: actual_burn_amount,
+            development_fee,
+        };
+        record_burner(deps.storage, &recipient, Uint128::zero(), &paid_rewards)?;
+        record_burn_stats(
+            deps.storage,
+            env.block.time.seconds(),
+            Some(&recipient),
+            Uint128::zero(),
+            &[],
+            &paid_rewards,
+            Some(&token_burn),
+        )?;
+        if let Some(referrer) = &referrer {
+            record_referrer(deps.storage, referrer, Uint128::zero(), &referral_earnings)?;
+            attrs.push(Attribute {
+                key: "referrer".to_string(),
+                value: referrer.to_string(),
+            });
+        }
+        let record_id = next_burn_record_id(deps.storage)?;
+        let record = BurnRecord {
+            id: record_id,
+            burner: recipient.clone(),
+            swap_input: None,
+            referrer,
+            token_burn: Some(token_burn),
+            burned: vec![],
+            development_fee: vec![],
+            rewards: paid_rewards,
+            time: env.block.time.seconds(),
+        };
+        burn_records().save(deps.storage, record_id, &record)?;
+
+        let mut res = Response::new()
+            .add_messages(fee_msgs)
+            .add_messages(referral_fee_msgs);
+        if !actual_burn_amount.is_zero() {
+            res = res.add_message(WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Burn {
+                    amount: actual_burn_amount,
+                })?,
+                funds: vec![],
+            });
+        }
+
+        let res = res
+            .add_messages(reward_msgs)
+            .add_attribute("action", "burn_token")
+            .add_attribute("record_id", record_id.to_string())
+            .add_attribute("token", token)
+            .add_attribute("recipient", recipient)
+            .add_attribute("development_fee", development_fee)
+            .add_attribute("burn_amount", actual_burn_amount)
+            .add_attributes(attrs);
+        Ok(res)
+    }
+
+    // pays or vests each reward up to the unreserved balance, splitting off the referrer's share
+    fn distribute_rewards(
+        deps: DepsMut,
+        env: &Env,
+        recipient: &Addr,
+        rewards: Vec<(AssetInfo, Uint128)>,
+        referral: Option<&(Addr, ReferralConfig)>,
+        attrs: &mut Vec<Attribute>,
+        referral_earnings: &mut Vec<BurnerReward>,
+    ) -> StdResult<(Vec<CosmosMsg>, Vec<BurnerReward>)> {
+        let vesting_duration = REWARD_VESTING_DURATION.may_load(deps.storage)?;
+        let mut reward_msgs = vec![];
+        let mut paid_rewards = vec![];
+        for (asset_info, mut reward_amount) in rewards {
+            if reward_amount.is_zero() {
+                continue;
+            }
+            let key = asset_info.to_string();
+
+            let available = available_reward(deps.as_ref(), &asset_info, &env.contract.address)?;
+            if available.is_zero() {
+                attrs.push(Attribute {
+                    key: "reward_depleted".to_string(),
+                    value: key,
+                });
+                continue;
+            }
+
+            if reward_amount >= available {
+                reward_amount = available;
+                attrs.push(Attribute {
+                    key: "reward_depleted".to_string(),
+                    value: key.clone(),
+                });
+            }
+
+            REWARDS_PAID.update(
+                deps.storage,
+                &key,
+                |paid: Option<Uint128>| -> StdResult<_> {
+                    Ok(paid.unwrap_or_default() + reward_amount)
+                },
+            )?;
+
+            let mut payouts = vec![];
+            if let Some((referrer, _)) = referral {
+                let referral_reward =
+                    referral_share(referral, ReferralSource::Rewards, reward_amount);
+                if !referral_reward.is_zero() {
+                    reward_amount -= referral_reward;
+                    payouts.push((referrer, referral_reward));
+                    attrs.push(Attribute {
+                        key: "referral_reward".to_string(),
+                        value: referral_reward.to_string(),
+                    });
+                    referral_earnings.push(BurnerReward {
+                        asset_info: asset_info.clone(),
+                        amount: referral_reward,
+                    });
+                }
+            }
+            if !reward_amount.is_zero() {
+                payouts.push((recipient, reward_amount));
+            }
+            for (receiver, amount) in payouts {
+                match vesting_duration {
+                    Some(duration) => vest_reward(
+                        deps.storage,
+                        receiver,
+                        &asset_info,
+                        amount,
+                        env.block.time.seconds(),
+                        duration,
+                    )?,
+                    None => reward_msgs.push(create_transfer_msg(&asset_info, receiver, amount)?),
+                }
+            }
+
+            attrs.push(Attribute {
+                key: "token".to_string(),
+                value: key.clone(),
+            });
+            attrs.push(Attribute {
+                key: "reward".to_string(),
+                value: reward_amount.to_string(),
+            });
+            paid_rewards.push(BurnerReward {
+                asset_info,
+                amount: reward_amount,
+            });
+        }
+        Ok((reward_msgs, paid_rewards))
+    }
+
+    // the last beneficiary also receives the rounding remainder
+    fn split_development_fee(
+        fee_beneficiaries: &[FeeBeneficiary],
+        fee_coins: Vec<Coin>,
+    ) -> Vec<BankMsg> {
+        let total_weight: u64 = fee_beneficiaries
+            .iter()
+            .map(|beneficiary| beneficiary.weight)
+            .sum();
+        let mut remaining_coins = fee_coins.clone();
+        let mut fee_msgs = vec![];
+        for (i, beneficiary) in fee_beneficiaries.iter().enumerate() {
+            let amount = if i + 1 == fee_beneficiaries.len() {
+                remaining_coins.clone()
+            } else {
+                fee_coins
+                    .iter()
+                    .zip(remaining_coins.iter_mut())
+                    .map(|(fee_coin, remaining_coin)| {
+                        let share = fee_coin
+                            .amount
+                            .multiply_ratio(beneficiary.weight, total_weight);
+                        remaining_coin.amount -= share;
+                        Coin {
+                            denom: fee_coin.denom.clone(),
+                            amount: share,
+                        }
+                    })
+                    .collect()
+            };
+            let amount = amount
+                .into_iter()
+                .filter(|coin| !coin.amount.is_zero())
+                .collect::<Vec<_>>();
+            if !amount.is_empty() {
+                fee_msgs.push(BankMsg::Send {
+                    to_address: beneficiary.address.to_string(),
+                    amount,
+                });
+            }
+        }
+        fee_msgs
+    }
+
+    // splits a cw20 development fee like split_development_fee
+    fn split_token_fee(
+        fee_beneficiaries: &[FeeBeneficiary],
+        token_asset: &AssetInfo,
+        fee: Uint128,
+    ) -> StdResult<Vec<CosmosMsg>> {
+        let total_weight: u64 = fee_beneficiaries
+            .iter()
+            .map(|beneficiary| beneficiary.weight)
+            .sum();
+        let mut remaining = fee;
+        let mut fee_msgs = vec![];
+        for (i, beneficiary) in fee_beneficiaries.iter().enumerate() {
+            let amount = if i + 1 == fee_beneficiaries.len() {
+                remaining
+            } else {
+                let share = fee.multiply_ratio(beneficiary.weight, total_weight);
+                remaining -= share;
+                share
+            };
+            if !amount.is_zero() {
+                fee_msgs.push(create_transfer_msg(
+                    token_asset,
+                    &beneficiary.address,
+                    amount,
+                )?);
+            }
+        }
+        Ok(fee_msgs)
+    }
+
+    // new rewards restart the linear unlock of everything still locked
+    fn vest_reward(
+        storage: &mut dyn Storage,
+        burner: &Addr,
+        asset_info: &AssetInfo,
+        amount: Uint128,
+        now: u64,
+        duration: u64,
+    ) -> StdResult<()> {
+        let key = asset_info.to_string();
+        let mut vesting = REWARD_VESTINGS
+            .may_load(storage, (burner, &key))?
+            .unwrap_or(RewardVesting {
+                asset_info: asset_info.clone(),
+                claimable: Uint128::zero(),
+                locked: Uint128::zero(),
+                last_update: now,
+                end_time: now,
+            });
+        vesting.settle(now);
+        vesting.locked += amount;
+        vesting.end_time = now + duration;
+        REWARD_VESTINGS.save(storage, (burner, &key), &vesting)?;
+        VESTING_REWARD_TOTALS.update(storage, &key, |total: Option<Uint128>| -> StdResult<_> {
+            Ok(total.unwrap_or_default() + amount)
+        })?;
+        Ok(())
+    }
+
+    fn next_burn_record_id(storage: &mut dyn Storage) -> StdResult<u64> {
+        let record_id = BURN_RECORD_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+        BURN_RECORD_COUNT.save(storage, &record_id)?;
+        Ok(record_id)
+    }
+
+    fn record_burner(
+        storage: &mut dyn Storage,
+        burner: &Addr,
+        burned_amount: Uint128,
+        paid_rewards: &[BurnerReward],
+    ) -> StdResult<()> {
+        burners().update(
+            storage,
+            burner.clone(),
+            |stats: Option<BurnerStats>| -> StdResult<_> {
+                let mut stats = stats.unwrap_or(BurnerStats {
+                    burned_amount: Uint128::zero(),
+                    rewards: vec![],
+                });
+                stats.burned_amount += burned_amount;
+                add_rewards(&mut stats.rewards, paid_rewards);
+                Ok(stats)
+            },
+        )?;
+        Ok(())
+    }
+
+    fn record_referrer(
+        storage: &mut dyn Storage,
+        referrer: &Addr,
+        referred_amount: Uint128,
+        earnings: &[BurnerReward],
+    ) -> StdResult<()> {
+        REFERRERS.update(
+            storage,
+            referrer,
+            |stats: Option<ReferrerStats>| -> StdResult<_> {
+                let mut stats = stats.unwrap_or(ReferrerStats {
+                    referred_amount: Uint128::zero(),
+                    referral_count: 0,
+                    earnings: vec![],
+                });
+                stats.referred_amount += referred_amount;
+                stats.referral_count += 1;
+                add_rewards(&mut stats.earnings, earnings);
+                Ok(stats)
+            },
+        )?;
+        Ok(())
+    }
+
+    // reward shares of referrals recorded when they were referred
+    fn add_referral_earnings(
+        storage: &mut dyn Storage,
+        referrer: &Addr,
+        earnings: &[BurnerReward],
+    ) -> StdResult<()> {
+        if earnings.is_empty() {
+            return Ok(());
+        }
+        REFERRERS.update(storage, referrer, |stats| -> StdResult<_> {
+            let mut stats = stats.unwrap_or(ReferrerStats {
+                referred_amount: Uint128::zero(),
+                referral_count: 0,
+                earnings: vec![],
+            });
+            add_rewards(&mut stats.earnings, earnings);
+            Ok(stats)
+        })?;
+        Ok(())
+    }
+
+    // adds to the day and week buckets of now, a burner counts the call as a burn
+    fn record_burn_stats(
+        storage: &mut dyn Storage,
+        now: u64,
+        burner: Option<&Addr>,
+        burned_luna: Uint128,
+        development_fee: &[Coin],
+        rewards: &[BurnerReward],
+        token_burn: Option<&TokenBurn>,
+    ) -> StdResult<()> {
+        for (burn_stats, burners, bucket) in [
+            (DAILY_BURN_STATS, DAILY_BURNERS, now / DAY),
+            (WEEKLY_BURN_STATS, WEEKLY_BURNERS, now / WEEK),
+        ] {
+            let mut stats = burn_stats.may_load(storage, bucket)?.unwrap_or_default();
+            stats.burned_luna += burned_luna;
+            for fee_coin in development_fee {
+                add_coin(&mut stats.development_fee, &fee_coin.denom, fee_coin.amount);
+            }
+            add_rewards(&mut stats.rewards, rewards);
+            if let Some(token_burn) = token_burn {
+                match stats
+                    .token_burns
+                    .iter_mut()
+                    .find(|total| total.token == token_burn.token)
+                {
+                    Some(total) => {
+                        total.burned += token_burn.burned;
+                        total.development_fee += token_burn.development_fee;
+                    }
+                    None => stats.token_burns.push(token_burn.clone()),
+                }
+            }
+            if let Some(burner) = burner {
+                stats.burn_count += 1;
+                if !burners.has(storage, (bucket, burner)) {
+                    burners.save(storage, (bucket, burner), &true)?;
+                    stats.burner_count += 1;
+                }
+            }
+            burn_stats.save(storage, bucket, &stats)?;
+        }
+        Ok(())
+    }
+
+    fn add_rewards(totals: &mut Vec<BurnerReward>, rewards: &[BurnerReward]) {
+        for reward in rewards {
+            match totals
+                .iter_mut()
+                .find(|total| total.asset_info == reward.asset_info)
+            {
+                Some(total) => total.amount += reward.amount,
+                None => totals.push(reward.clone()),
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap_and_burn(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        swap_paths: Vec<SwapHop>,
+        minimum_receive: Option<Uint128>,
+        deadline: Option<u64>,
+        referrer: Option<String>,
+        recipient: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let funds = info.funds.clone();
+        let mut swap_amount = Uint128::zero();
+        for coin in funds {
+            if coin.denom == denom {
+                swap_amount = coin.amount;
+                break;
+            }
+        }
+        if swap_amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+
+        let recipient = validate_recipient(deps.as_ref(), recipient)?;
+        let referrer = validate_referrer(
+            deps.as_ref(),
+            referrer,
+            recipient.as_ref().unwrap_or(&info.sender),
+        )?;
+        let offer_asset = AssetInfo::NativeToken {
+            denom: denom.clone(),
+        };
+        let ask_asset = AssetInfo::NativeToken {
+            denom: "uluna".to_string(),
+        };
+        let swap_runs = create_swap_runs(deps.storage, offer_asset, &ask_asset, swap_paths)?;
+
+        let swap_context = SwapContext {
+            burner: info.sender,
+            recipient,
+            swap_input: Some(SwapInput {
+                asset_info: AssetInfo::NativeToken { denom },
+                amount: swap_amount,
+            }),
+            referrer,
+            minimum_receive,
+            deadline,
+            pre_swap_balance: None,
+            pending_runs: swap_runs,
+        };
+        let swap_id = save_swap_context(deps.storage, &swap_context)?;
+
+        let tax = swap_amount * LUNC_TAX;
+        let actual_swap_amount = swap_amount - tax;
+        swap_next_run(deps, &env, swap_id, swap_context, actual_swap_amount)
+    }
+
+    // swap `amount` through the next pending run, replying to `swap_id`. Only the last run
+    // is held to minimum_receive, the earlier ones feed the next run in reply
+    pub fn swap_next_run(
+        deps: DepsMut,
+        env: &Env,
+        swap_id: u64,
+        mut swap_context: SwapContext,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        let swap_run = swap_context.pending_runs.remove(0);
+        let minimum_receive = match swap_context.pending_runs.is_empty() {
+            true => swap_context.minimum_receive,
+            false => None,
+        };
+        let swap_msg = swap_run_msg(&swap_run, amount, minimum_receive, swap_context.deadline)?;
+
+        swap_context.pre_swap_balance = Some(query_asset_balance(
+            &deps.querier,
+            &swap_run.ask_asset_info,
+            &env.contract.address,
+        )?);
+        SWAP_CONTEXTS.save(deps.storage, swap_id, &swap_context)?;
+
+        let swap_submsg = SubMsg::reply_on_success(swap_msg, swap_id);
+        Ok(Response::new().add_submessage(swap_submsg))
+    }
+
+    // the reply id of the swap submessage, so each reply resolves its own context
+    fn save_swap_context(storage: &mut dyn Storage, swap_context: &SwapContext) -> StdResult<u64> {
+        let swap_id = LAST_SWAP_ID.may_load(storage)?.unwrap_or_default() + 1;
+        LAST_SWAP_ID.save(storage, &swap_id)?;
+        SWAP_CONTEXTS.save(storage, swap_id, swap_context)?;
+        Ok(swap_id)
+    }
+
+    pub fn receive_cw20(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        cw20_msg: Cw20ReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        let token_in = info.sender;
+        let amount = cw20_msg.amount;
+
+        let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+
+        match from_json(&cw20_msg.msg) {
+            Ok(Cw20HookMsg::SwapAndBurn {
+                swap_paths,
+                minimum_receive,
+                deadline,
+                referrer,
+                recipient,
+            }) => {
+                let recipient = validate_recipient(deps.as_ref(), recipient)?;
+                let referrer = validate_referrer(
+                    deps.as_ref(),
+                    referrer,
+                    recipient.as_ref().unwrap_or(&sender),
+                )?;
+                let offer_asset = AssetInfo::Token {
+                    contract_addr: token_in.to_string(),
+                };
+                let ask_asset = AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                };
+                let swap_runs =
+                    create_swap_runs(deps.storage, offer_asset.clone(), &ask_asset, swap_paths)?;
+
+                let swap_context = SwapContext {
+                    burner: sender,
+                    recipient,
+                    swap_input: Some(SwapInput {
+                        asset_info: offer_asset,
+                        amount,
+                    }),
+                    referrer,
+                    minimum_receive,
+                    deadline,
+                    pre_swap_balance: None,
+                    pending_runs: swap_runs,
+                };
+                let swap_id = save_swap_context(deps.storage, &swap_context)?;
+                swap_next_run(deps, &env, swap_id, swap_context, amount)
+            }
+            Ok(Cw20HookMsg::Burn {
+                referrer,
+                recipient,
+            }) => {
+                let recipient = validate_recipient(deps.as_ref(), recipient)?;
+                let recipient = recipient.unwrap_or(sender);
+                let referrer = validate_referrer(deps.as_ref(), referrer, &recipient)?;
+                burn_token(deps, env, token_in, recipient, amount, referrer)
+            }
+            Ok(Cw20HookMsg::DepositRewards {}) => {
+                if !REWARD_ASSETS.has(deps.storage, token_in.as_str()) {
+                    return Err(ContractError::NotInWhitelist {});
+                }
+                if amount.is_zero() {
+                    return Err(ContractError::ZeroAmount {});
+                }
+
+                let deposited = REWARD_DEPOSITS.update(
+                    deps.storage,
+                    token_in.as_str(),
+                    |deposited: Option<Uint128>| -> StdResult<_> {
+                        Ok(deposited.unwrap_or_default() + amount)
+                    },
+                )?;
+
+                let res = Response::new()
+                    .add_attribute("action", "deposit_rewards")
+                    .add_attribute("token", token_in)
+                    .add_attribute("depositor", sender)
+                    .add_attribute("amount", amount)
+                    .add_attribute("deposited", deposited);
+                Ok(res)
+            }
+            Err(err) => Err(ContractError::StdError(err)),
+        }
+    }
+}
+
+mod query {
+    use super::*;
+
+    pub fn owner(deps: Deps) -> StdResult<OwnerResponse> {
+        let owner = OWNER.load(deps.storage)?;
+        Ok(OwnerResponse { owner })
+    }
+
+    pub fn development_config(deps: Deps) -> StdResult<DevelopmentConfigResponse> {
         let fee_ratio = DEVELOPMENT_FEE_RATIO.load(deps.storage)?;
-        let fee_beneficiary = DEVELOPMENT_FEE_BENEFICIARY.load(deps.storage)?;
+        // reports the first beneficiary for clients of the single beneficiary config
+        let fee_beneficiaries = DEVELOPMENT_FEE_BENEFICIARIES.load(deps.storage)?;
+        let beneficiary = fee_beneficiaries
+            .first()
+            .ok_or_else(|| StdError::generic_err("No fee beneficiaries"))?;
         let development_config = DevelopmentConfig {
             fee_ratio,
-            beneficiary: fee_beneficiary.to_string(),
+            beneficiary: beneficiary.address.to_string(),
         };
         Ok(DevelopmentConfigResponse(development_config))
     }
 
+    pub fn fee_beneficiaries(deps: Deps) -> StdResult<FeeBeneficiariesResponse> {
+        let fee_ratio = DEVELOPMENT_FEE_RATIO.load(deps.storage)?;
+        let beneficiaries = DEVELOPMENT_FEE_BENEFICIARIES.load(deps.storage)?;
+        Ok(FeeBeneficiariesResponse {
+            fee_ratio,
+            beneficiaries,
+        })
+    }
+
     pub fn reward_whitelist(deps: Deps) -> StdResult<RewardWhitelistResponse> {
-        let reward_whitelist = REWARD_WHITELIST
-            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+        let reward_whitelist = REWARD_ASSETS
+            .range(deps.storage, None, None, Order::Ascending)
             .map(|item| {
-                item.map(|(token, reward_ratio)| RewardInfo {
-                    token: token.to_string(),
-                    reward_ratio,
+                item.map(|(_, reward_asset)| RewardInfo {
+                    asset_info: reward_asset.asset_info,
+                    reward_ratio: reward_asset.reward_ratio,
                 })
             })
             .collect::<StdResult<Vec<_>>>()?;
@@ -512,17 +2389,416 @@ mod query {
     }
 
     pub fn burned_amount(deps: Deps) -> StdResult<BurnedAmountResponse> {
-        let burned_amount = BURNED_AMOUNT.load(deps.storage)?;
+        let burned_amount = BURNED_AMOUNTS
+            .may_load(deps.storage, "uluna")?
+            .unwrap_or_default();
         Ok(BurnedAmountResponse { burned_amount })
     }
 
+    pub fn burned_amounts(deps: Deps) -> StdResult<BurnedAmountsResponse> {
+        let burned_amounts = BURNED_AMOUNTS
+            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BurnedAmountsResponse { burned_amounts })
+    }
+
+    pub fn burnable_denoms(deps: Deps) -> StdResult<BurnableDenomsResponse> {
+        let denoms = BURNABLE_DENOMS
+            .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
+            .map(|item| {
+                item.map(|(denom, burnable_denom)| BurnableDenomInfo {
+                    denom,
+                    fee_ratio: burnable_denom.fee_ratio,
+                    reward_weight: burnable_denom.reward_weight,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BurnableDenomsResponse { denoms })
+    }
+
+    pub fn burnable_tokens(deps: Deps) -> StdResult<BurnableTokensResponse> {
+        let tokens = BURNABLE_TOKENS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(token, burnable_token)| BurnableTokenInfo {
+                    token: token.to_string(),
+                    fee_ratio: burnable_token.fee_ratio,
+                    reward_weight: burnable_token.reward_weight,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BurnableTokensResponse { tokens })
+    }
+
+    pub fn burned_tokens(deps: Deps) -> StdResult<BurnedTokensResponse> {
+        let burned_tokens = BURNED_TOKENS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(token, amount)| BurnedToken { token, amount }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BurnedTokensResponse { burned_tokens })
+    }
+
+    pub fn burner_info(deps: Deps, address: String) -> StdResult<BurnerInfoResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let stats = burners().may_load(deps.storage, address.clone())?;
+        let (burned_amount, rewards) = match stats {
+            Some(stats) => (stats.burned_amount, stats.rewards),
+            None => (Uint128::zero(), vec![]),
+        };
+        Ok(BurnerInfoResponse {
+            address,
+            burned_amount,
+            rewards,
+        })
+    }
+
+    pub fn burn_history(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        burner: Option<String>,
+    ) -> StdResult<BurnHistoryResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let min = start_after.map(Bound::exclusive);
+
+        let records = match burner {
+            Some(burner) => {
+                let burner = deps.api.addr_validate(&burner)?;
+                burn_records()
+                    .idx
+                    .burner
+                    .prefix(burner)
+                    .range(deps.storage, min, None, Order::Ascending)
+                    .take(limit)
+                    .map(|item| item.map(|(_, record)| record))
+                    .collect::<StdResult<Vec<_>>>()?
+            }
+            None => burn_records()
+                .range(deps.storage, min, None, Order::Ascending)
+                .take(limit)
+                .map(|item| item.map(|(_, record)| record))
+                .collect::<StdResult<Vec<_>>>()?,
+        };
+        Ok(BurnHistoryResponse { records })
+    }
+
+    pub fn top_burners(
+        deps: Deps,
+        limit: Option<u32>,
+        start_after: Option<String>,
+    ) -> StdResult<TopBurnersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let max = match start_after {
+            Some(start_after) => {
+                let address = deps.api.addr_validate(&start_after)?;
+                let stats = burners().load(deps.storage, address.clone())?;
+                Some(Bound::exclusive((stats.burned_amount.u128(), address)))
+            }
+            None => None,
+        };
+
+        // burners only credited with rewards or token burns rank last and are left out
+        let burners = burners()
+            .idx
+            .burned_amount
+            .range(deps.storage, None, max, Order::Descending)
+            .take_while(|item| {
+                item.as_ref()
+                    .map_or(true, |(_, stats)| !stats.burned_amount.is_zero())
+            })
+            .take(limit)
+            .map(|item| {
+                item.map(|(address, stats)| BurnerInfoResponse {
+                    address,
+                    burned_amount: stats.burned_amount,
+                    rewards: stats.rewards,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(TopBurnersResponse { burners })
+    }
+
     pub fn swap_router(deps: Deps) -> StdResult<SwapRouterResponse> {
-        let swap_router = SWAP_ROUTER.load(deps.storage);
-        match swap_router {
-            Ok(router) => Ok(SwapRouterResponse {
-                swap_router: Some(router),
-            }),
-            Err(_) => Ok(SwapRouterResponse { swap_router: None }),
+        let routers = SWAP_ROUTERS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, router)| router))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(SwapRouterResponse { routers })
+    }
+
+    pub fn reward_vesting(deps: Deps) -> StdResult<RewardVestingResponse> {
+        let duration = REWARD_VESTING_DURATION.may_load(deps.storage)?;
+        Ok(RewardVestingResponse { duration })
+    }
+
+    pub fn pending_rewards(
+        deps: Deps,
+        env: Env,
+        address: String,
+    ) -> StdResult<PendingRewardsResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let now = env.block.time.seconds();
+        let rewards = REWARD_VESTINGS
+            .prefix(&address)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(_, mut vesting)| {
+                    vesting.settle(now);
+                    PendingReward {
+                        asset_info: vesting.asset_info,
+                        vested: vesting.claimable,
+                        locked: vesting.locked,
+                    }
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(PendingRewardsResponse { rewards })
+    }
+
+    pub fn reward_reserves(deps: Deps, env: Env) -> StdResult<RewardReservesResponse> {
+        let reward_weight = BURNABLE_DENOMS
+            .may_load(deps.storage, "uluna")?
+            .map(|burnable_denom| burnable_denom.reward_weight)
+            .unwrap_or_default();
+        let reserves = REWARD_ASSETS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (key, reward_asset) = item?;
+                let balance = query_asset_balance(
+                    &deps.querier,
+                    &reward_asset.asset_info,
+                    &env.contract.address,
+                )?;
+                let reserved = reserved_reward(deps.storage, &key)?;
+                let rate = reward_asset.reward_ratio * reward_weight;
+                let coverable_burn_amount = (!rate.is_zero()).then(|| {
+                    balance
+                        .saturating_sub(reserved)
+                        .multiply_ratio(rate.denominator(), rate.numerator())
+                });
+                Ok(RewardReserve {
+                    asset_info: reward_asset.asset_info,
+                    balance,
+                    deposited: REWARD_DEPOSITS
+                        .may_load(deps.storage, &key)?
+                        .unwrap_or_default(),
+                    paid_out: REWARDS_PAID
+                        .may_load(deps.storage, &key)?
+                        .unwrap_or_default(),
+                    reserved,
+                    coverable_burn_amount,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(RewardReservesResponse { reserves })
+    }
+
+    pub fn reward_tiers(deps: Deps) -> StdResult<RewardTiersResponse> {
+        let reward_tiers = REWARD_TIERS.may_load(deps.storage)?;
+        Ok(RewardTiersResponse { reward_tiers })
+    }
+
+    pub fn referral_config(deps: Deps) -> StdResult<ReferralConfigResponse> {
+        let referral_config = REFERRAL_CONFIG.may_load(deps.storage)?;
+        Ok(ReferralConfigResponse { referral_config })
+    }
+
+    pub fn referrer_stats(deps: Deps, address: String) -> StdResult<ReferrerStatsResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let stats = REFERRERS
+            .may_load(deps.storage, &address)?
+            .unwrap_or(ReferrerStats {
+                referred_amount: Uint128::zero(),
+                referral_count: 0,
+                earnings: vec![],
+            });
+        Ok(ReferrerStatsResponse {
+            address,
+            referred_amount: stats.referred_amount,
+            referral_count: stats.referral_count,
+            earnings: stats.earnings,
+        })
+    }
+
+    pub fn epoch(deps: Deps, id: Option<u64>) -> StdResult<EpochResponse> {
+        let id = match id {
+            Some(id) => id,
+            None => LATEST_EPOCH.load(deps.storage)?,
+        };
+        let epoch = EPOCHS.load(deps.storage, id)?;
+        let end_time = match EPOCH_DURATION.may_load(deps.storage)? {
+            Some(duration) if !epoch.processed => Some(epoch.start_time + duration),
+            _ => None,
+        };
+        Ok(EpochResponse { epoch, end_time })
+    }
+
+    // deposits are removed once their rewards are claimed
+    pub fn epoch_share(
+        deps: Deps,
+        address: String,
+        id: Option<u64>,
+    ) -> StdResult<EpochShareResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let id = match id {
+            Some(id) => id,
+            None => LATEST_EPOCH.load(deps.storage)?,
+        };
+        let epoch = EPOCHS.load(deps.storage, id)?;
+        let deposit = EPOCH_DEPOSITS
+            .may_load(deps.storage, (&address, id))?
+            .unwrap_or(EpochDeposit {
+                burned: vec![],
+                development_fee: vec![],
+                weight: Uint128::zero(),
+            });
+
+        let share = if epoch.total_weight.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(deposit.weight, epoch.total_weight)
+        };
+        let rewards = if epoch.processed {
+            epoch.reward_share(deposit.weight)
+        } else {
+            vec![]
+        };
+        Ok(EpochShareResponse {
+            id,
+            burned: deposit.burned,
+            weight: deposit.weight,
+            share,
+            rewards,
+        })
+    }
+
+    pub fn burn_stats(deps: Deps, from_day: u64, to_day: u64) -> StdResult<BurnStatsResponse> {
+        if from_day > to_day {
+            return Err(StdError::generic_err("from_day is after to_day"));
+        }
+        let to_day = to_day.min(from_day.saturating_add(MAX_STATS_DAYS - 1));
+        let days = DAILY_BURN_STATS
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(from_day)),
+                Some(Bound::inclusive(to_day)),
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(id, stats)| BurnStatsBucket { id, stats }))
+            .collect::<StdResult<Vec<_>>>()?;
+        let weeks = WEEKLY_BURN_STATS
+            .range(
+                deps.storage,
+                Some(Bound::inclusive(from_day / (WEEK / DAY))),
+                Some(Bound::inclusive(to_day / (WEEK / DAY))),
+                Order::Ascending,
+            )
+            .map(|item| item.map(|(id, stats)| BurnStatsBucket { id, stats }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BurnStatsResponse { days, weeks })
+    }
+
+    // follows `burn`: the same fee split, tier multiplier, reward cap and referral cuts
+    pub fn simulate_burn(
+        deps: Deps,
+        env: Env,
+        denom: String,
+        amount: Uint128,
+        burner: Option<String>,
+        referrer: Option<String>,
+    ) -> StdResult<SimulateBurnResponse> {
+        let burner = burner
+            .map(|burner| deps.api.addr_validate(&burner))
+            .transpose()?;
+        let referrer = referrer
+            .map(|referrer| deps.api.addr_validate(&referrer))
+            .transpose()?;
+        if burner.is_some() && referrer == burner {
+            return Err(StdError::generic_err(
+                ContractError::SelfReferral {}.to_string(),
+            ));
+        }
+        let burnable_denom = BURNABLE_DENOMS
+            .may_load(deps.storage, &denom)?
+            .ok_or_else(|| {
+                StdError::generic_err(
+                    ContractError::NotBurnableDenom {
+                        denom: denom.clone(),
+                    }
+                    .to_string(),
+                )
+            })?;
+        let fee_ratio = match burnable_denom.fee_ratio {
+            Some(fee_ratio) => fee_ratio,
+            None => DEVELOPMENT_FEE_RATIO.load(deps.storage)?,
+        };
+        let (development_fee, burn_amount) = split_burn_amount(amount, fee_ratio);
+
+        let referral = load_referral(deps.storage, referrer.as_ref())?;
+        let mut referral_rewards = vec![];
+        let referral_fee = referral_share(
+            referral.as_ref(),
+            ReferralSource::DevelopmentFee,
+            development_fee,
+        );
+        if !referral_fee.is_zero() {
+            referral_rewards.push(BurnerReward {
+                asset_info: AssetInfo::NativeToken {
+                    denom: denom.clone(),
+                },
+                amount: referral_fee,
+            });
+        }
+
+        // epoch burns are only rewarded pro rata once their epoch is processed
+        if EPOCH_DURATION.exists(deps.storage) {
+            return Ok(SimulateBurnResponse {
+                development_fee,
+                burn_amount,
+                multiplier: Decimal::one(),
+                rewards: vec![],
+                referral_rewards,
+                epoch_mode: true,
+            });
+        }
+
+        // reward tiers are reached by burned uluna only
+        let burned_luna = if denom == "uluna" {
+            burn_amount
+        } else {
+            Uint128::zero()
+        };
+        let multiplier = reward_multiplier(deps.storage, burner.as_ref(), burned_luna)?;
+        let mut rewards = vec![];
+        for item in REWARD_ASSETS.range(deps.storage, None, None, Order::Ascending) {
+            let (_, reward_asset) = item?;
+            let reward_amount =
+                burn_amount * reward_asset.reward_ratio * burnable_denom.reward_weight * multiplier;
+            let available =
+                available_reward(deps, &reward_asset.asset_info, &env.contract.address)?;
+            let reward_amount = reward_amount.min(available);
+            let referral_reward =
+                referral_share(referral.as_ref(), ReferralSource::Rewards, reward_amount);
+            if !referral_reward.is_zero() {
+                referral_rewards.push(BurnerReward {
+                    asset_info: reward_asset.asset_info.clone(),
+                    amount: referral_reward,
+                });
+            }
+            rewards.push(BurnerReward {
+                asset_info: reward_asset.asset_info,
+                amount: reward_amount - referral_reward,
+            });
         }
+
+        Ok(SimulateBurnResponse {
+            development_fee,
+            burn_amount,
+            multiplier,
+            rewards,
+            referral_rewards,
+            epoch_mode: false,
+        })
     }
 }
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use cremation_token::swap::SwapPathError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -19,8 +20,49 @@ pub enum ContractError {
     ZeroRatio {},
     #[error("Invalid Reply Message")]
     InvalidReplyMsg {},
-    #[error("Locked")]
-    Locked {},
-    #[error("Already unlocked")]
-    AlreadyUnlocked {},
+    #[error("Not burnable denom: {denom}")]
+    NotBurnableDenom { denom: String },
+    #[error("Received {received} uluna, less than minimum receive {minimum_receive}")]
+    MinimumReceiveNotMet {
+        received: Uint128,
+        minimum_receive: Uint128,
+    },
+    #[error("Swap output denom cannot be removed")]
+    SwapDenomRequired {},
+    #[error("Invalid swap paths")]
+    InvalidSwapPaths {},
+    #[error("Swap router not set for {dex}")]
+    SwapRouterNotSet { dex: String },
+    #[error("Invalid fee beneficiaries")]
+    InvalidFeeBeneficiaries {},
+    #[error("Invalid reward tiers")]
+    InvalidRewardTiers {},
+    #[error("Zero vesting duration")]
+    ZeroVestingDuration {},
+    #[error("Burner cannot refer itself")]
+    SelfReferral {},
+    #[error("Invalid referral share")]
+    InvalidReferralShare {},
+    #[error("Zero epoch duration")]
+    ZeroEpochDuration {},
+    #[error("No open epoch")]
+    NoOpenEpoch {},
+    #[error("Epoch ends at {end_time}")]
+    EpochNotEnded { end_time: u64 },
+    #[error("Burnable denom cannot be a reward: {denom}")]
+    RewardDenomBurnable { denom: String },
+    #[error("Not burnable token: {token}")]
+    NotBurnableToken { token: String },
+    #[error("Burnable token cannot be a reward: {token}")]
+    RewardTokenBurnable { token: String },
+}
+
+impl From<SwapPathError> for ContractError {
+    fn from(err: SwapPathError) -> Self {
+        match err {
+            SwapPathError::StdError(err) => ContractError::StdError(err),
+            SwapPathError::InvalidSwapPaths {} => ContractError::InvalidSwapPaths {},
+            SwapPathError::SwapRouterNotSet { dex } => ContractError::SwapRouterNotSet { dex },
+        }
+    }
 }
@@ -1,34 +1,52 @@
-use cremation_token::msg::{AssetInfo, RouterExecuteMsg, SwapOperation};
+use cosmwasm_std::{
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, StdResult, Storage, Uint128, WasmMsg,
+};
+pub use cremation_token::swap::query_asset_balance;
+use cremation_token::{
+    msg::{AssetInfo, SwapHop},
+    swap::{self, SwapRun},
+};
+use cw20::Cw20ExecuteMsg;
 
-pub fn create_swap_operations(
-    offer_asset: AssetInfo,
-    ask_asset: AssetInfo,
-    swap_paths: Vec<AssetInfo>,
-) -> RouterExecuteMsg {
-    let mut operations = vec![];
-    for i in 0..=swap_paths.len() {
-        let offer_asset_info = if i == 0 {
-            offer_asset.clone()
-        } else {
-            swap_paths[i - 1].clone()
-        };
-
-        let ask_asset_info = if i == swap_paths.len() {
-            ask_asset.clone()
-        } else {
-            swap_paths[i].clone()
-        };
+use crate::{error::ContractError, state::SWAP_ROUTERS};
 
-        operations.push(SwapOperation::TerraPort {
-            offer_asset_info,
-            ask_asset_info,
-        });
-    }
+/// Splits the swap paths into runs through the routers set for their dexes.
+pub fn create_swap_runs(
+    storage: &dyn Storage,
+    offer_asset: AssetInfo,
+    ask_asset: &AssetInfo,
+    swap_paths: Vec<SwapHop>,
+) -> Result<Vec<SwapRun>, ContractError> {
+    let runs = swap::create_swap_runs(offer_asset, ask_asset, swap_paths, |dex| {
+        let router = SWAP_ROUTERS.may_load(storage, &dex.to_string())?;
+        Ok(router.map(|router| router.router))
+    })?;
+    Ok(runs)
+}
 
-    RouterExecuteMsg::ExecuteSwapOperations {
-        operations,
-        to: None,
-        minimum_receive: None,
-        deadline: None,
-    }
+pub fn create_transfer_msg(
+    asset_info: &AssetInfo,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<CosmosMsg> {
+    let msg = match asset_info {
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        AssetInfo::NativeToken { denom } => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        }
+        .into(),
+    };
+    Ok(msg)
 }
@@ -1,9 +1,15 @@
 use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+    entry_point, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response,
+    StdResult,
 };
+use cremation_token::msg::{AssetInfo, SwapDex};
 use error::ContractError;
 use msg::*;
-use state::{CachedData, CACHE};
+use state::{
+    BurnableDenom, FeeBeneficiary, RewardAsset, SwapRouterInfo, BURNABLE_DENOMS, BURNED_AMOUNT,
+    BURNED_AMOUNTS, CACHE, DEVELOPMENT_FEE_BENEFICIARIES, DEVELOPMENT_FEE_BENEFICIARY,
+    REWARD_ASSETS, REWARD_WHITELIST, SWAP_ROUTER, SWAP_ROUTERS,
+};
 
 pub mod contract;
 pub mod error;
@@ -12,14 +18,60 @@ pub mod msg;
 pub mod state;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    CACHE.save(
-        deps.storage,
-        &&CachedData {
-            locked: false,
-            burner: env.contract.address,
-        },
-    )?;
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    CACHE.remove(deps.storage);
+
+    if !BURNABLE_DENOMS.has(deps.storage, "uluna") {
+        BURNABLE_DENOMS.save(
+            deps.storage,
+            "uluna",
+            &BurnableDenom {
+                fee_ratio: None,
+                reward_weight: Decimal::one(),
+            },
+        )?;
+    }
+    if let Some(burned_amount) = BURNED_AMOUNT.may_load(deps.storage)? {
+        BURNED_AMOUNTS.save(deps.storage, "uluna", &burned_amount)?;
+        BURNED_AMOUNT.remove(deps.storage);
+    }
+    if let Some(router) = SWAP_ROUTER.may_load(deps.storage)? {
+        SWAP_ROUTERS.save(
+            deps.storage,
+            &SwapDex::Terraport.to_string(),
+            &SwapRouterInfo {
+                dex: SwapDex::Terraport,
+                router,
+            },
+        )?;
+        SWAP_ROUTER.remove(deps.storage);
+    }
+    if let Some(beneficiary) = DEVELOPMENT_FEE_BENEFICIARY.may_load(deps.storage)? {
+        DEVELOPMENT_FEE_BENEFICIARIES.save(
+            deps.storage,
+            &vec![FeeBeneficiary {
+                address: beneficiary,
+                weight: 1,
+            }],
+        )?;
+        DEVELOPMENT_FEE_BENEFICIARY.remove(deps.storage);
+    }
+    let reward_whitelist = REWARD_WHITELIST
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (token, reward_ratio) in reward_whitelist {
+        REWARD_ASSETS.save(
+            deps.storage,
+            token.as_str(),
+            &RewardAsset {
+                asset_info: AssetInfo::Token {
+                    contract_addr: token.to_string(),
+                },
+                reward_ratio,
+            },
+        )?;
+        REWARD_WHITELIST.remove(deps.storage, token);
+    }
 
     Ok(Response::default())
 }
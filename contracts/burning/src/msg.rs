@@ -1,17 +1,49 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cremation_token::msg::AssetInfo;
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cremation_token::msg::{AssetInfo, SwapDex, SwapHop};
 use cw20::Cw20ReceiveMsg;
 
+use crate::state::{
+    BurnRecord, BurnStats, BurnerReward, Epoch, FeeBeneficiary, ReferralConfig, RewardTier,
+    RewardTierBasis, RewardTiers, SwapRouterInfo,
+};
+
 #[cw_serde]
 pub struct RewardInfo {
-    pub token: String,
+    pub asset_info: AssetInfo,
     pub reward_ratio: Decimal,
 }
 
+#[cw_serde]
+pub struct BurnableDenomInfo {
+    pub denom: String,
+    pub fee_ratio: Option<Decimal>,
+    pub reward_weight: Decimal,
+}
+
+#[cw_serde]
+pub struct BurnableTokenInfo {
+    pub token: String,
+    pub fee_ratio: Option<Decimal>,
+    pub reward_weight: Decimal,
+}
+
 #[cw_serde]
 pub enum Cw20HookMsg {
-    SwapAndBurn { swap_paths: Vec<AssetInfo> },
+    SwapAndBurn {
+        swap_paths: Vec<SwapHop>,
+        minimum_receive: Option<Uint128>,
+        deadline: Option<u64>,
+        referrer: Option<String>,
+        recipient: Option<String>,
+    },
+    /// Funds the reward pool of a whitelisted reward token
+    DepositRewards {},
+    /// Burns the received burnable token, bypassing epochs
+    Burn {
+        referrer: Option<String>,
+        recipient: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -20,6 +52,12 @@ pub struct DevelopmentConfig {
     pub fee_ratio: Decimal,
 }
 
+#[cw_serde]
+pub struct FeeBeneficiaryInfo {
+    pub address: String,
+    pub weight: u64,
+}
+
 #[cw_serde]
 pub struct MigrateMsg {}
 
@@ -35,22 +73,77 @@ pub enum ExecuteMsg {
         beneficiary: Option<String>,
         fee_ratio: Option<Decimal>,
     },
+    /// Replaces the development fee beneficiaries, splitting the fee by weight
+    SetFeeBeneficiaries {
+        beneficiaries: Vec<FeeBeneficiaryInfo>,
+    },
     AddToRewardWhitelist {
         reward_info: RewardInfo,
     },
     RemoveFromRewardWhitelist {
-        token: String,
+        asset_info: AssetInfo,
     },
     UpdateRewardInfo {
         reward_info: RewardInfo,
     },
-    Burn {},
+    /// Vests burn rewards linearly over `duration` seconds, or sends them right away when unset
+    UpdateRewardVesting {
+        duration: Option<u64>,
+    },
+    ClaimRewards {},
+    /// Multiplies burn rewards by the highest tier reached, no tiers restores flat rewards
+    SetRewardTiers {
+        basis: RewardTierBasis,
+        tiers: Vec<RewardTier>,
+    },
+    /// Gives referrers a share of referred burns, no config disables referral payouts
+    SetReferralConfig {
+        referral_config: Option<ReferralConfig>,
+    },
+    /// Deposits burns into epochs of `duration` seconds, or burns them right away when unset
+    SetEpochDuration {
+        duration: Option<u64>,
+    },
+    /// Burns the deposits of the open epoch once it has ended and allots its rewards
+    ProcessEpoch {},
+    /// Sends the sender's share of the rewards of every processed epoch it deposited in
+    ClaimEpochRewards {},
+    /// Funds the reward pools of whitelisted native denoms with the sent coins
+    DepositRewards {},
+    SetBurnableDenom {
+        denom_info: BurnableDenomInfo,
+    },
+    RemoveBurnableDenom {
+        denom: String,
+    },
+    SetBurnableToken {
+        token_info: BurnableTokenInfo,
+    },
+    RemoveBurnableToken {
+        token: String,
+    },
+    /// Burns the sent coins, crediting the burn and its rewards to `recipient` when set
+    Burn {
+        referrer: Option<String>,
+        recipient: Option<String>,
+    },
     SetSwapRouter {
+        dex: SwapDex,
         router: String,
     },
     SwapAndBurn {
-        swap_paths: Vec<AssetInfo>,
+        swap_paths: Vec<SwapHop>,
+        denom: String,
+        minimum_receive: Option<Uint128>,
+        deadline: Option<u64>,
+        referrer: Option<String>,
+        recipient: Option<String>,
+    },
+    /// Sends the contract's stray balance of a native denom, e.g. uluna sent by mistake, to the recipient.
+    /// Deposits of the open epoch and rewards reserved for burners are kept.
+    Sweep {
         denom: String,
+        recipient: String,
     },
     Receive(Cw20ReceiveMsg),
 }
@@ -60,14 +153,73 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(OwnerResponse)]
     Owner {},
+    /// Deprecated: only reports the first of the fee beneficiaries, use `FeeBeneficiaries {}`.
     #[returns(DevelopmentConfigResponse)]
     DevelopmentConfig {},
+    #[returns(FeeBeneficiariesResponse)]
+    FeeBeneficiaries {},
     #[returns(RewardWhitelistResponse)]
     RewardWhitelist {},
     #[returns(BurnedAmountResponse)]
     BurnedAmount {},
+    #[returns(BurnedAmountsResponse)]
+    BurnedAmounts {},
+    #[returns(BurnableDenomsResponse)]
+    BurnableDenoms {},
+    #[returns(BurnableTokensResponse)]
+    BurnableTokens {},
+    /// Burned amount of each cw20 token burned through the receive hook.
+    #[returns(BurnedTokensResponse)]
+    BurnedTokens {},
+    #[returns(BurnerInfoResponse)]
+    BurnerInfo { address: String },
+    /// Burn records in chronological order, optionally only those of the burner.
+    #[returns(BurnHistoryResponse)]
+    BurnHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        burner: Option<String>,
+    },
+    /// Burners ordered by cumulative burned uluna, highest first.
+    #[returns(TopBurnersResponse)]
+    TopBurners {
+        limit: Option<u32>,
+        start_after: Option<String>,
+    },
     #[returns(SwapRouterResponse)]
     SwapRouter {},
+    #[returns(RewardVestingResponse)]
+    RewardVesting {},
+    /// Vested and locked rewards of the burner per reward token.
+    #[returns(PendingRewardsResponse)]
+    PendingRewards { address: String },
+    /// Reward pool of each whitelisted token and the uluna burns it still covers.
+    #[returns(RewardReservesResponse)]
+    RewardReserves {},
+    #[returns(RewardTiersResponse)]
+    RewardTiers {},
+    #[returns(ReferralConfigResponse)]
+    ReferralConfig {},
+    /// Volume referred by the address and what it earned from referrals.
+    #[returns(ReferrerStatsResponse)]
+    ReferrerStats { address: String },
+    /// The epoch with the id, or the latest epoch.
+    #[returns(EpochResponse)]
+    Epoch { id: Option<u64> },
+    /// Deposit and reward share of the address in the epoch, or in the latest epoch.
+    #[returns(EpochShareResponse)]
+    EpochShare { address: String, id: Option<u64> },
+    /// Daily stats of the days in the range and weekly stats of the weeks they fall in.
+    #[returns(BurnStatsResponse)]
+    BurnStats { from_day: u64, to_day: u64 },
+    /// Fee, net burn and rewards of burning `amount` of the burnable `denom` now, referred by `referrer`.
+    #[returns(SimulateBurnResponse)]
+    SimulateBurn {
+        denom: String,
+        amount: Uint128,
+        burner: Option<String>,
+        referrer: Option<String>,
+    },
 }
 
 #[cw_serde]
@@ -78,6 +230,12 @@ pub struct OwnerResponse {
 #[cw_serde]
 pub struct DevelopmentConfigResponse(pub DevelopmentConfig);
 
+#[cw_serde]
+pub struct FeeBeneficiariesResponse {
+    pub fee_ratio: Decimal,
+    pub beneficiaries: Vec<FeeBeneficiary>,
+}
+
 #[cw_serde]
 pub struct RewardWhitelistResponse {
     pub reward_whitelist: Vec<RewardInfo>,
@@ -88,7 +246,144 @@ pub struct BurnedAmountResponse {
     pub burned_amount: Uint128,
 }
 
+#[cw_serde]
+pub struct BurnedAmountsResponse {
+    pub burned_amounts: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct BurnableDenomsResponse {
+    pub denoms: Vec<BurnableDenomInfo>,
+}
+
+#[cw_serde]
+pub struct BurnableTokensResponse {
+    pub tokens: Vec<BurnableTokenInfo>,
+}
+
+#[cw_serde]
+pub struct BurnedToken {
+    pub token: Addr,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct BurnedTokensResponse {
+    pub burned_tokens: Vec<BurnedToken>,
+}
+
+#[cw_serde]
+pub struct BurnerInfoResponse {
+    pub address: Addr,
+    pub burned_amount: Uint128,
+    pub rewards: Vec<BurnerReward>,
+}
+
+#[cw_serde]
+pub struct TopBurnersResponse {
+    pub burners: Vec<BurnerInfoResponse>,
+}
+
+#[cw_serde]
+pub struct BurnHistoryResponse {
+    pub records: Vec<BurnRecord>,
+}
+
 #[cw_serde]
 pub struct SwapRouterResponse {
-    pub swap_router: Option<Addr>,
+    pub routers: Vec<SwapRouterInfo>,
+}
+
+#[cw_serde]
+pub struct RewardVestingResponse {
+    pub duration: Option<u64>,
+}
+
+#[cw_serde]
+pub struct PendingReward {
+    pub asset_info: AssetInfo,
+    pub vested: Uint128,
+    pub locked: Uint128,
+}
+
+#[cw_serde]
+pub struct PendingRewardsResponse {
+    pub rewards: Vec<PendingReward>,
+}
+
+#[cw_serde]
+pub struct RewardReserve {
+    pub asset_info: AssetInfo,
+    pub balance: Uint128,
+    // owed to burners with vesting or unclaimed epoch rewards
+    pub reserved: Uint128,
+    pub deposited: Uint128,
+    pub paid_out: Uint128,
+    // uluna burns the unreserved balance covers, none if the token pays no uluna rewards
+    pub coverable_burn_amount: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct RewardReservesResponse {
+    pub reserves: Vec<RewardReserve>,
+}
+
+#[cw_serde]
+pub struct RewardTiersResponse {
+    pub reward_tiers: Option<RewardTiers>,
+}
+
+#[cw_serde]
+pub struct SimulateBurnResponse {
+    pub development_fee: Uint128,
+    pub burn_amount: Uint128,
+    pub multiplier: Decimal,
+    // what the burner receives, after the referrer's cut
+    pub rewards: Vec<BurnerReward>,
+    pub referral_rewards: Vec<BurnerReward>,
+    // burns go into an epoch and are rewarded once it is processed, so rewards are empty
+    pub epoch_mode: bool,
+}
+
+#[cw_serde]
+pub struct ReferralConfigResponse {
+    pub referral_config: Option<ReferralConfig>,
+}
+
+#[cw_serde]
+pub struct ReferrerStatsResponse {
+    pub address: Addr,
+    pub referred_amount: Uint128,
+    pub referral_count: u64,
+    pub earnings: Vec<BurnerReward>,
+}
+
+#[cw_serde]
+pub struct EpochResponse {
+    pub epoch: Epoch,
+    // none once processed or when epochs are disabled
+    pub end_time: Option<u64>,
+}
+
+#[cw_serde]
+pub struct EpochShareResponse {
+    pub id: u64,
+    pub burned: Vec<Coin>,
+    pub weight: Uint128,
+    pub share: Decimal,
+    // claimable rewards, empty until the epoch is processed
+    pub rewards: Vec<BurnerReward>,
+}
+
+#[cw_serde]
+pub struct BurnStatsBucket {
+    // days or weeks since the unix epoch
+    pub id: u64,
+    pub stats: BurnStats,
+}
+
+#[cw_serde]
+pub struct BurnStatsResponse {
+    pub days: Vec<BurnStatsBucket>,
+    pub weeks: Vec<BurnStatsBucket>,
 }
@@ -1,22 +1,342 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Decimal, Empty, Uint128};
+use cremation_token::{
+    msg::{AssetInfo, SwapDex},
+    swap::SwapRun,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 pub const OWNER: Item<Addr> = Item::new("owner");
 
+// legacy cw20 reward whitelist, moved into REWARD_ASSETS on migrate
 pub const REWARD_WHITELIST: Map<Addr, Decimal> = Map::new("reward_whitelist");
 
+#[cw_serde]
+pub struct RewardAsset {
+    pub asset_info: AssetInfo,
+    pub reward_ratio: Decimal,
+}
+
+// reward maps are keyed by the token address or native denom of the reward asset
+pub const REWARD_ASSETS: Map<&str, RewardAsset> = Map::new("reward_assets");
+
+// seconds over which burn rewards unlock linearly, rewards are sent right away when unset
+pub const REWARD_VESTING_DURATION: Item<u64> = Item::new("reward_vesting_duration");
+
+// vesting rewards of a burner in one reward token
+#[cw_serde]
+pub struct RewardVesting {
+    pub asset_info: AssetInfo,
+    pub claimable: Uint128,
+    pub locked: Uint128,
+    pub last_update: u64,
+    pub end_time: u64,
+}
+
+impl RewardVesting {
+    // moves the part of locked that unlocked since the last update into claimable
+    pub fn settle(&mut self, now: u64) {
+        if now >= self.end_time {
+            self.claimable += self.locked;
+            self.locked = Uint128::zero();
+        } else if now > self.last_update {
+            let unlocked = self
+                .locked
+                .multiply_ratio(now - self.last_update, self.end_time - self.last_update);
+            self.claimable += unlocked;
+            self.locked -= unlocked;
+        }
+        self.last_update = now;
+    }
+}
+
+// rewards funded through deposits and paid or vested to burners, per token
+pub const REWARD_DEPOSITS: Map<&str, Uint128> = Map::new("reward_deposits");
+pub const REWARDS_PAID: Map<&str, Uint128> = Map::new("rewards_paid");
+
+// what a reward tier's min_amount is compared against, both in uluna
+#[cw_serde]
+pub enum RewardTierBasis {
+    // burned in the current burn
+    Transaction,
+    // burned by the burner so far, including the current burn
+    Cumulative,
+}
+
+#[cw_serde]
+pub struct RewardTier {
+    pub min_amount: Uint128,
+    pub multiplier: Decimal,
+}
+
+// tiers ordered by min_amount, burns below the first tier keep a multiplier of 1
+#[cw_serde]
+pub struct RewardTiers {
+    pub basis: RewardTierBasis,
+    pub tiers: Vec<RewardTier>,
+}
+
+pub const REWARD_TIERS: Item<RewardTiers> = Item::new("reward_tiers");
+
+// what the referrer's share of a referred burn is taken from
+#[cw_serde]
+pub enum ReferralSource {
+    // each whitelisted reward paid for the burn
+    Rewards,
+    // the development fee of the burn
+    DevelopmentFee,
+}
+
+#[cw_serde]
+pub struct ReferralConfig {
+    pub source: ReferralSource,
+    pub share: Decimal,
+}
+
+pub const REFERRAL_CONFIG: Item<ReferralConfig> = Item::new("referral_config");
+
+// keyed by (burner, token)
+pub const REWARD_VESTINGS: Map<(&Addr, &str), RewardVesting> = Map::new("reward_vestings");
+// rewards owed to burners per token, reserved from the contract's reward balance
+pub const VESTING_REWARD_TOTALS: Map<&str, Uint128> = Map::new("vesting_reward_totals");
+
+// seconds per burn epoch, burns are deposited into the open epoch instead of burned when set
+pub const EPOCH_DURATION: Item<u64> = Item::new("epoch_duration");
+
+// deposits of an epoch, burned together and rewarded pro rata by ProcessEpoch
+#[cw_serde]
+pub struct Epoch {
+    pub id: u64,
+    pub start_time: u64,
+    pub burned: Vec<Coin>,
+    pub development_fee: Vec<Coin>,
+    // the referrers' share of the development fee, paid out on deposit
+    pub referral_fee: Vec<Coin>,
+    // sum of the depositors' reward weighted burn amounts
+    pub total_weight: Uint128,
+    pub depositor_count: u64,
+    pub processed: bool,
+    pub rewards: Vec<BurnerReward>,
+    // left for depositors that have not claimed yet
+    pub unclaimed_weight: Uint128,
+    pub unclaimed_rewards: Vec<BurnerReward>,
+}
+
+impl Epoch {
+    // rewards owed for weight out of the unclaimed weight, so the last claim takes the remainder
+    pub fn reward_share(&self, weight: Uint128) -> Vec<BurnerReward> {
+        if self.unclaimed_weight.is_zero() {
+            return vec![];
+        }
+        self.unclaimed_rewards
+            .iter()
+            .map(|reward| BurnerReward {
+                asset_info: reward.asset_info.clone(),
+                amount: reward.amount.multiply_ratio(weight, self.unclaimed_weight),
+            })
+            .filter(|reward| !reward.amount.is_zero())
+            .collect()
+    }
+}
+
+pub const LATEST_EPOCH: Item<u64> = Item::new("latest_epoch");
+pub const EPOCHS: Map<u64, Epoch> = Map::new("epochs");
+
+#[cw_serde]
+pub struct EpochDeposit {
+    pub burned: Vec<Coin>,
+    pub development_fee: Vec<Coin>,
+    pub weight: Uint128,
+}
+
+// keyed by (depositor, epoch)
+pub const EPOCH_DEPOSITS: Map<(&Addr, u64), EpochDeposit> = Map::new("epoch_deposits");
+// weight of the referred deposits keyed by (depositor, epoch, referrer), for the referral reward share
+pub const EPOCH_REFERRALS: Map<(&Addr, u64, &Addr), Uint128> = Map::new("epoch_referrals");
+// rewards of processed epochs not yet claimed per token, reserved like vesting rewards
+pub const EPOCH_REWARD_TOTALS: Map<&str, Uint128> = Map::new("epoch_reward_totals");
+
+// legacy uluna counter, moved into BURNED_AMOUNTS on migrate
 pub const BURNED_AMOUNT: Item<Uint128> = Item::new("burned_amount");
+pub const BURNED_AMOUNTS: Map<&str, Uint128> = Map::new("burned_amounts");
+
+#[cw_serde]
+pub struct BurnableDenom {
+    // overrides the development fee ratio for burns of this denom
+    pub fee_ratio: Option<Decimal>,
+    // scales the reward whitelist ratios for burns of this denom
+    pub reward_weight: Decimal,
+}
+
+pub const BURNABLE_DENOMS: Map<&str, BurnableDenom> = Map::new("burnable_denoms");
+// cw20 tokens burned directly through the receive hook
+pub const BURNABLE_TOKENS: Map<&Addr, BurnableDenom> = Map::new("burnable_tokens");
+pub const BURNED_TOKENS: Map<&Addr, Uint128> = Map::new("burned_tokens");
+
+#[cw_serde]
+pub struct BurnerReward {
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+}
+
+// cumulative uluna burned and rewards received by a burner, cw20 token burns only add their rewards
+#[cw_serde]
+pub struct BurnerStats {
+    pub burned_amount: Uint128,
+    pub rewards: Vec<BurnerReward>,
+}
+
+// uluna burned through a referrer's referrals and what the referrer earned from them
+#[cw_serde]
+pub struct ReferrerStats {
+    pub referred_amount: Uint128,
+    pub referral_count: u64,
+    pub earnings: Vec<BurnerReward>,
+}
+
+pub const REFERRERS: Map<&Addr, ReferrerStats> = Map::new("referrers");
+
+pub struct BurnerIndexes<'a> {
+    pub burned_amount: MultiIndex<'a, u128, BurnerStats, Addr>,
+}
+
+impl<'a> IndexList<BurnerStats> for BurnerIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<BurnerStats>> + '_> {
+        let v: Vec<&dyn Index<BurnerStats>> = vec![&self.burned_amount];
+        Box::new(v.into_iter())
+    }
+}
+
+#[cw_serde]
+pub struct BurnRecord {
+    pub id: u64,
+    pub burner: Addr,
+    // the swapped asset of a swap and burn
+    pub swap_input: Option<SwapInput>,
+    pub referrer: Option<Addr>,
+    // the cw20 of a token burn, `burned` and `development_fee` only hold native coins
+    pub token_burn: Option<TokenBurn>,
+    pub burned: Vec<Coin>,
+    pub development_fee: Vec<Coin>,
+    pub rewards: Vec<BurnerReward>,
+    pub time: u64,
+}
+
+#[cw_serde]
+pub struct TokenBurn {
+    pub token: Addr,
+    pub burned: Uint128,
+    // split before transfer, a taxed token delivers it to beneficiaries net of its transfer tax
+    pub development_fee: Uint128,
+}
+
+pub const BURN_RECORD_COUNT: Item<u64> = Item::new("burn_record_count");
+
+pub struct BurnRecordIndexes<'a> {
+    pub burner: MultiIndex<'a, Addr, BurnRecord, u64>,
+}
+
+impl<'a> IndexList<BurnRecord> for BurnRecordIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<BurnRecord>> + '_> {
+        let v: Vec<&dyn Index<BurnRecord>> = vec![&self.burner];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn burn_records<'a>() -> IndexedMap<'a, u64, BurnRecord, BurnRecordIndexes<'a>> {
+    let indexes = BurnRecordIndexes {
+        burner: MultiIndex::new(
+            |_pk, record| record.burner.clone(),
+            "burn_records",
+            "burn_records__burner",
+        ),
+    };
+    IndexedMap::new("burn_records", indexes)
+}
+
+pub fn burners<'a>() -> IndexedMap<'a, Addr, BurnerStats, BurnerIndexes<'a>> {
+    let indexes = BurnerIndexes {
+        burned_amount: MultiIndex::new(
+            |_pk, stats| stats.burned_amount.u128(),
+            "burners",
+            "burners__burned_amount",
+        ),
+    };
+    IndexedMap::new("burners", indexes)
+}
+
+// what was burned in a day or week, for charts
+#[cw_serde]
+#[derive(Default)]
+pub struct BurnStats {
+    // epoch deposits count when their epoch is processed and their depositors when they claim
+    pub burned_luna: Uint128,
+    pub burn_count: u64,
+    pub burner_count: u64,
+    // native development fees
+    pub development_fee: Vec<Coin>,
+    // rewards paid or vested to burners, epoch rewards count when the epoch is processed
+    pub rewards: Vec<BurnerReward>,
+    // cw20 token burns, summed per token
+    pub token_burns: Vec<TokenBurn>,
+}
+
+// keyed by block time / 86400 and by block time / 604800 (weeks since the unix epoch)
+pub const DAILY_BURN_STATS: Map<u64, BurnStats> = Map::new("daily_burn_stats");
+pub const WEEKLY_BURN_STATS: Map<u64, BurnStats> = Map::new("weekly_burn_stats");
+// burners already counted in a day or week
+pub const DAILY_BURNERS: Map<(u64, &Addr), bool> = Map::new("daily_burners");
+pub const WEEKLY_BURNERS: Map<(u64, &Addr), bool> = Map::new("weekly_burners");
 
 pub const DEVELOPMENT_FEE_RATIO: Item<Decimal> = Item::new("development_fee");
+// legacy single beneficiary, moved into DEVELOPMENT_FEE_BENEFICIARIES on migrate
 pub const DEVELOPMENT_FEE_BENEFICIARY: Item<Addr> = Item::new("fee_beneficiary");
 
+// the development fee is split between beneficiaries in proportion to their weights
+#[cw_serde]
+pub struct FeeBeneficiary {
+    pub address: Addr,
+    pub weight: u64,
+}
+
+pub const DEVELOPMENT_FEE_BENEFICIARIES: Item<Vec<FeeBeneficiary>> = Item::new("fee_beneficiaries");
+
+// legacy single router, moved into SWAP_ROUTERS as the terraport router on migrate
 pub const SWAP_ROUTER: Item<Addr> = Item::new("swap_router");
 
 #[cw_serde]
-pub struct CachedData {
-    pub locked: bool,
+pub struct SwapRouterInfo {
+    pub dex: SwapDex,
+    pub router: Addr,
+}
+
+pub const SWAP_ROUTERS: Map<&str, SwapRouterInfo> = Map::new("swap_routers");
+
+#[cw_serde]
+pub struct SwapInput {
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct SwapContext {
     pub burner: Addr,
+    // credited with the burn instead of the burner when set
+    pub recipient: Option<Addr>,
+    pub swap_input: Option<SwapInput>,
+    pub referrer: Option<Addr>,
+    // checked against the uluna received from the swap in reply
+    pub minimum_receive: Option<Uint128>,
+    pub deadline: Option<u64>,
+    // balance of the ask asset of the running swap run before it, so reply takes only what
+    // the run produced, uluna for the last run
+    pub pre_swap_balance: Option<Uint128>,
+    // runs through other routers still to swap, each fed the output of the previous one
+    pub pending_runs: Vec<SwapRun>,
 }
 
-pub const CACHE: Item<CachedData> = Item::new("cache");
+// swap contexts keyed by the reply id of their swap submessage, removed in reply
+pub const SWAP_CONTEXTS: Map<u64, SwapContext> = Map::new("swap_contexts");
+pub const LAST_SWAP_ID: Item<u64> = Item::new("last_swap_id");
+// legacy single swap slot, removed on migrate
+pub const CACHE: Item<Empty> = Item::new("cache");
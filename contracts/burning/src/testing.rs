@@ -1,16 +1,28 @@
 use cosmwasm_std::{
     coin, from_json,
     testing::{mock_dependencies, mock_env, mock_info},
-    Addr, Coin, Decimal, Uint128,
+    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, Deps, Env, Reply, StdError,
+    SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
 };
-use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20Coin, Cw20QueryMsg};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20Coin, Cw20QueryMsg, Cw20ReceiveMsg};
 use cw20_base::contract::{
     execute as cw20_execute, instantiate as cw20_instantiate, query as cw20_query,
 };
 use cw_multi_test::{App, ContractWrapper, Executor};
 use std::time::SystemTime;
 
-use crate::{contract::LUNC_TAX, error::ContractError, execute, instantiate, msg::*, query};
+use crate::{
+    contract::LUNC_TAX,
+    error::ContractError,
+    execute, instantiate,
+    msg::*,
+    query, reply,
+    state::{
+        BurnStats, BurnerReward, FeeBeneficiary, ReferralConfig, ReferralSource, RewardTier,
+        RewardTierBasis, SwapInput, TokenBurn, DEVELOPMENT_FEE_BENEFICIARIES,
+    },
+};
+use cremation_token::msg::{AssetInfo, RouterExecuteMsg, SwapDex, SwapHop, SwapOperation};
 
 mod helpers {
     use cosmwasm_std::{
@@ -33,7 +45,9 @@ mod helpers {
             let token = format!("token{}", i);
             let reward_ratio = Decimal::percent((i + 1) * 10);
             let reward_info = RewardInfo {
-                token: token.to_string(),
+                asset_info: AssetInfo::Token {
+                    contract_addr: token.to_string(),
+                },
                 reward_ratio,
             };
             reward_list.push(reward_info);
@@ -124,7 +138,9 @@ mod helpers {
             let token_addr = token_addrs[i].clone();
             let reward_ratio = Decimal::percent((i + 1) as u64 * 10);
             let reward_info = RewardInfo {
-                token: token_addr.to_string(),
+                asset_info: AssetInfo::Token {
+                    contract_addr: token_addr.to_string(),
+                },
                 reward_ratio,
             };
             reward_list.push(reward_info.clone());
@@ -206,7 +222,9 @@ fn add_to_reward_whitelist_properly() {
     let token = "token";
     let reward_ratio = Decimal::percent(10);
     let reward_info = RewardInfo {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
         reward_ratio,
     };
     let msg = ExecuteMsg::AddToRewardWhitelist { reward_info };
@@ -226,7 +244,9 @@ fn add_to_reward_whitelist_properly() {
     let reward_whitelist_res: RewardWhitelistResponse = from_json(&reward_whitelist_query).unwrap();
     assert_eq!(reward_whitelist_res.reward_whitelist.len(), 1);
     assert_eq!(
-        reward_whitelist_res.reward_whitelist[0].token,
+        reward_whitelist_res.reward_whitelist[0]
+            .asset_info
+            .to_string(),
         token.to_string()
     );
     assert_eq!(
@@ -262,7 +282,9 @@ fn fail_to_add_to_reward_whitelist_not_authorized() {
     let token = "token";
     let reward_ratio = Decimal::percent(10);
     let reward_info = RewardInfo {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
         reward_ratio,
     };
     let msg = ExecuteMsg::AddToRewardWhitelist { reward_info };
@@ -295,7 +317,9 @@ fn fail_to_add_to_reward_whitelist_with_zero_ratio() {
     let token = "token";
     let reward_ratio = Decimal::zero();
     let reward_info = RewardInfo {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
         reward_ratio,
     };
     let msg = ExecuteMsg::AddToRewardWhitelist { reward_info };
@@ -365,9 +389,9 @@ fn remove_from_reward_whitelist_properly() {
 
     let rand_id = helpers::psuedo_rand(reward_list_len);
     let reward_info = reward_list[rand_id].to_owned();
-    let token = reward_info.token.to_string();
+    let token = reward_info.asset_info.to_string();
     let msg = ExecuteMsg::RemoveFromRewardWhitelist {
-        token: token.clone(),
+        asset_info: reward_info.asset_info.clone(),
     };
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
     assert_eq!(2, res.attributes.len());
@@ -386,7 +410,7 @@ fn remove_from_reward_whitelist_properly() {
 
     // check reward not in whitelist
     for reward_info in reward_whitelist_res.reward_whitelist {
-        assert_ne!(reward_info.token, token);
+        assert_ne!(reward_info.asset_info.to_string(), token);
     }
 }
 
@@ -411,7 +435,9 @@ fn fail_to_remove_from_reward_whitelist_not_authorized() {
     let token = "token";
     let reward_ratio = Decimal::percent(10);
     let reward_info = RewardInfo {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
         reward_ratio,
     };
     let msg = ExecuteMsg::AddToRewardWhitelist { reward_info };
@@ -420,7 +446,9 @@ fn fail_to_remove_from_reward_whitelist_not_authorized() {
 
     let info = mock_info("anyone", &[]);
     let msg = ExecuteMsg::RemoveFromRewardWhitelist {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
     };
     let res = execute(deps.as_mut(), env, info, msg);
     assert!(res.is_err());
@@ -452,7 +480,9 @@ fn fail_to_remove_from_reward_whitelist_not_in_whitelist() {
     let info = mock_info(owner, &[]);
     let token = "token-not-in-whitelist";
     let msg = ExecuteMsg::RemoveFromRewardWhitelist {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
     };
     let res = execute(deps.as_mut(), env, info, msg);
     assert!(res.is_err());
@@ -502,8 +532,8 @@ fn update_reward_info_properly() {
         reward_list_len as usize
     );
     assert_eq!(
-        reward_whitelist_res.reward_whitelist[rand_id].token,
-        reward_info.token.to_string()
+        reward_whitelist_res.reward_whitelist[rand_id].asset_info,
+        reward_info.asset_info
     );
     assert_eq!(
         reward_whitelist_res.reward_whitelist[rand_id].reward_ratio,
@@ -605,7 +635,9 @@ fn fail_to_update_reward_info_not_in_whitelist() {
     let token = "token-not-in-whitelist";
     let reward_ratio = Decimal::percent(10);
     let reward_info = RewardInfo {
-        token: token.to_string(),
+        asset_info: AssetInfo::Token {
+            contract_addr: token.to_string(),
+        },
         reward_ratio,
     };
     let msg = ExecuteMsg::UpdateRewardInfo {
@@ -654,7 +686,10 @@ fn burn_properly() {
         .execute_contract(
             Addr::unchecked(burner),
             burning_addr.clone(),
-            &ExecuteMsg::Burn {},
+            &ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
             &vec![Coin {
                 denom: "uluna".to_string(),
                 amount: burn_amount,
@@ -696,7 +731,7 @@ fn burn_properly() {
         let reward_token_balance: Cw20BalanceResponse = app
             .wrap()
             .query_wasm_smart(
-                Addr::unchecked(reward.token),
+                Addr::unchecked(reward.asset_info.to_string()),
                 &Cw20QueryMsg::Balance {
                     address: burner.to_string(),
                 },
@@ -721,7 +756,10 @@ fn fail_to_burn_zero_amount() {
     let res = app.execute_contract(
         Addr::unchecked(burner),
         burning_addr.clone(),
-        &ExecuteMsg::Burn {},
+        &ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
         &vec![],
     );
     assert!(res.is_err());
@@ -764,7 +802,10 @@ fn burn_not_enough_rewards() {
     app.execute_contract(
         Addr::unchecked(burner),
         burning_addr.clone(),
-        &ExecuteMsg::Burn {},
+        &ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
         &vec![Coin {
             denom: "uluna".to_string(),
             amount: burn_amount,
@@ -795,7 +836,7 @@ fn burn_not_enough_rewards() {
         let reward_token_balance: Cw20BalanceResponse = app
             .wrap()
             .query_wasm_smart(
-                Addr::unchecked(reward.token),
+                Addr::unchecked(reward.asset_info.to_string()),
                 &Cw20QueryMsg::Balance {
                     address: burner.to_string(),
                 },
@@ -845,7 +886,10 @@ fn burned_amount_should_increase() {
         app.execute_contract(
             Addr::unchecked(burner),
             burning_addr.clone(),
-            &ExecuteMsg::Burn {},
+            &ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
             &vec![Coin {
                 denom: "uluna".to_string(),
                 amount: burn_amount,
@@ -866,3 +910,2832 @@ fn burned_amount_should_increase() {
         assert_eq!(beneficiary_balance.amount, total_development_fee);
     }
 }
+
+// 5e. burn multiple denoms
+#[test]
+fn burn_multiple_denoms() {
+    let owner = "owner";
+    let burner = "burner";
+
+    let burner_funds = vec![
+        coin(1_000_000, "uluna"),
+        coin(1_000_000, "uusd"),
+        coin(1_000_000, "ukrw"),
+    ];
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(burner), burner_funds.clone())
+            .unwrap();
+    });
+
+    let development_config = DevelopmentConfig {
+        fee_ratio: Decimal::percent(2),
+        beneficiary: "beneficiary".to_string(),
+    };
+    let setup_res = helpers::setup_multi_test_contracts(
+        &mut app,
+        owner,
+        1_000_000_000,
+        Some(development_config.clone()),
+    );
+    let burning_addr = setup_res.burning_addr;
+    let reward_list = setup_res.reward_list;
+
+    // uusd without development fee and half rewards
+    app.execute_contract(
+        Addr::unchecked(owner),
+        burning_addr.clone(),
+        &ExecuteMsg::SetBurnableDenom {
+            denom_info: BurnableDenomInfo {
+                denom: "uusd".to_string(),
+                fee_ratio: Some(Decimal::zero()),
+                reward_weight: Decimal::percent(50),
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    let burnable_denoms: BurnableDenomsResponse = app
+        .wrap()
+        .query_wasm_smart(burning_addr.clone(), &QueryMsg::BurnableDenoms {})
+        .unwrap();
+    assert_eq!(burnable_denoms.denoms.len(), 2);
+
+    // fail to burn a denom which is not burnable
+    let err = app
+        .execute_contract(
+            Addr::unchecked(burner),
+            burning_addr.clone(),
+            &ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
+            &[coin(1000, "uluna"), coin(1000, "ukrw")],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::NotBurnableDenom {
+            denom: "ukrw".to_string()
+        },
+        err.downcast().unwrap()
+    );
+
+    // fail to remove the swap output denom
+    let err = app
+        .execute_contract(
+            Addr::unchecked(owner),
+            burning_addr.clone(),
+            &ExecuteMsg::RemoveBurnableDenom {
+                denom: "uluna".to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::SwapDenomRequired {}, err.downcast().unwrap());
+
+    // burn uluna and uusd in one call
+    let burn_amount = Uint128::new(100_000);
+    app.execute_contract(
+        Addr::unchecked(burner),
+        burning_addr.clone(),
+        &ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+        &[
+            coin(burn_amount.u128(), "uluna"),
+            coin(burn_amount.u128(), "uusd"),
+        ],
+    )
+    .unwrap();
+
+    let development_fee = burn_amount * development_config.fee_ratio;
+    let actual_luna_burned = burn_amount - development_fee - development_fee * LUNC_TAX;
+    let burned_amounts: BurnedAmountsResponse = app
+        .wrap()
+        .query_wasm_smart(burning_addr.clone(), &QueryMsg::BurnedAmounts {})
+        .unwrap();
+    assert_eq!(
+        burned_amounts.burned_amounts,
+        vec![
+            coin(actual_luna_burned.u128(), "uluna"),
+            coin(burn_amount.u128(), "uusd"),
+        ]
+    );
+
+    // development fee only in uluna
+    let beneficiary_balances = app
+        .wrap()
+        .query_all_balances(development_config.beneficiary)
+        .unwrap();
+    assert_eq!(
+        beneficiary_balances,
+        vec![coin(development_fee.u128(), "uluna")]
+    );
+
+    // rewards of both denoms, uusd weighted by half
+    for reward in reward_list {
+        let reward_token_balance: Cw20BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                Addr::unchecked(reward.asset_info.to_string()),
+                &Cw20QueryMsg::Balance {
+                    address: burner.to_string(),
+                },
+            )
+            .unwrap();
+        let expected_reward_amount = actual_luna_burned * reward.reward_ratio
+            + burn_amount * reward.reward_ratio * Decimal::percent(50);
+        assert_eq!(reward_token_balance.balance, expected_reward_amount);
+    }
+}
+
+// 5f. vest rewards and claim the unlocked part
+#[test]
+fn vest_and_claim_rewards() {
+    let owner = "owner";
+    let burner = "burner";
+    let other_burner = "other_burner";
+
+    let mut app = App::new(|router, _, storage| {
+        for address in [burner, other_burner] {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(address),
+                    vec![coin(10_000, "uluna")],
+                )
+                .unwrap();
+        }
+    });
+
+    let setup_res = helpers::setup_multi_test_contracts(
+        &mut app,
+        owner,
+        1_000,
+        Some(DevelopmentConfig {
+            fee_ratio: Decimal::zero(),
+            beneficiary: "beneficiary".to_string(),
+        }),
+    );
+    let burning_addr = setup_res.burning_addr;
+    let reward_token = Addr::unchecked(setup_res.reward_list[0].asset_info.to_string());
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(owner),
+            burning_addr.clone(),
+            &ExecuteMsg::UpdateRewardVesting { duration: Some(0) },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroVestingDuration {}
+    );
+    app.execute_contract(
+        Addr::unchecked(owner),
+        burning_addr.clone(),
+        &ExecuteMsg::UpdateRewardVesting {
+            duration: Some(100),
+        },
+        &[],
+    )
+    .unwrap();
+
+    // the reward of 10% of the burned amount is locked
+    app.execute_contract(
+        Addr::unchecked(burner),
+        burning_addr.clone(),
+        &ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+        &[coin(10_000, "uluna")],
+    )
+    .unwrap();
+    let reward_balance: Cw20BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            reward_token.clone(),
+            &Cw20QueryMsg::Balance {
+                address: burner.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(reward_balance.balance, Uint128::zero());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(50));
+    let pending_rewards: PendingRewardsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            burning_addr.clone(),
+            &QueryMsg::PendingRewards {
+                address: burner.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        pending_rewards.rewards[0],
+        PendingReward {
+            asset_info: AssetInfo::Token {
+                contract_addr: reward_token.to_string(),
+            },
+            vested: Uint128::new(500),
+            locked: Uint128::new(500),
+        }
+    );
+
+    // rewards owed to the first burner are not paid out again
+    app.execute_contract(
+        Addr::unchecked(other_burner),
+        burning_addr.clone(),
+        &ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+        &[coin(10_000, "uluna")],
+    )
+    .unwrap();
+    let pending_rewards: PendingRewardsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            burning_addr.clone(),
+            &QueryMsg::PendingRewards {
+                address: other_burner.to_string(),
+            },
+        )
+        .unwrap();
+    assert!(pending_rewards.rewards.is_empty());
+
+    app.execute_contract(
+        Addr::unchecked(burner),
+        burning_addr.clone(),
+        &ExecuteMsg::ClaimRewards {},
+        &[],
+    )
+    .unwrap();
+    let reward_balance: Cw20BalanceResponse = app
+        .wrap()
+        .query_wasm_smart(
+            reward_token.clone(),
+            &Cw20QueryMsg::Balance {
+                address: burner.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(reward_balance.balance, Uint128::new(500));
+
+    // nothing more to claim until more unlocks
+    let err = app
+        .execute_contract(
+            Addr::unchecked(burner),
+            burning_addr,
+            &ExecuteMsg::ClaimRewards {},
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::ZeroAmount {}
+    );
+}
+
+// 5g. fund the reward pool and report reserves
+#[test]
+fn deposit_rewards_and_query_reserves() {
+    let owner = "owner";
+    let burner = "burner";
+
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked(burner),
+                vec![coin(5_000, "uluna")],
+            )
+            .unwrap();
+    });
+    let setup_res = helpers::setup_multi_test_contracts(
+        &mut app,
+        owner,
+        1_000,
+        Some(DevelopmentConfig {
+            fee_ratio: Decimal::zero(),
+            beneficiary: "beneficiary".to_string(),
+        }),
+    );
+    let burning_addr = setup_res.burning_addr;
+    let first_token = Addr::unchecked(setup_res.reward_list[0].asset_info.to_string());
+    let last_token = Addr::unchecked(setup_res.reward_list[9].asset_info.to_string());
+
+    // the last token pays 100% of the burn and runs out
+    let res = app
+        .execute_contract(
+            Addr::unchecked(burner),
+            burning_addr.clone(),
+            &ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
+            &[coin(5_000, "uluna")],
+        )
+        .unwrap();
+    let depleted = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .filter(|attr| attr.key == "reward_depleted")
+        .map(|attr| attr.value.clone())
+        .collect::<Vec<_>>();
+    assert!(depleted.contains(&last_token.to_string()));
+    assert!(!depleted.contains(&first_token.to_string()));
+
+    let reserves: RewardReservesResponse = app
+        .wrap()
+        .query_wasm_smart(burning_addr.clone(), &QueryMsg::RewardReserves {})
+        .unwrap();
+    let first_reserve = reserves
+        .reserves
+        .iter()
+        .find(|reserve| reserve.asset_info.to_string() == first_token.as_str())
+        .unwrap();
+    assert_eq!(first_reserve.balance, Uint128::new(500));
+    assert_eq!(first_reserve.paid_out, Uint128::new(500));
+    assert_eq!(
+        first_reserve.coverable_burn_amount,
+        Some(Uint128::new(5_000))
+    );
+    let last_reserve = reserves
+        .reserves
+        .iter()
+        .find(|reserve| reserve.asset_info.to_string() == last_token.as_str())
+        .unwrap();
+    assert_eq!(last_reserve.balance, Uint128::zero());
+    assert_eq!(last_reserve.paid_out, Uint128::new(1_000));
+    assert_eq!(last_reserve.coverable_burn_amount, Some(Uint128::zero()));
+
+    // refill the depleted pool through a deposit
+    app.execute_contract(
+        Addr::unchecked(burner),
+        last_token.clone(),
+        &cw20::Cw20ExecuteMsg::Send {
+            contract: burning_addr.to_string(),
+            amount: Uint128::new(1_000),
+            msg: to_json_binary(&Cw20HookMsg::DepositRewards {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    let reserves: RewardReservesResponse = app
+        .wrap()
+        .query_wasm_smart(burning_addr, &QueryMsg::RewardReserves {})
+        .unwrap();
+    let last_reserve = reserves
+        .reserves
+        .iter()
+        .find(|reserve| reserve.asset_info.to_string() == last_token.as_str())
+        .unwrap();
+    assert_eq!(last_reserve.balance, Uint128::new(1_000));
+    assert_eq!(last_reserve.deposited, Uint128::new(1_000));
+    assert_eq!(
+        last_reserve.coverable_burn_amount,
+        Some(Uint128::new(1_000))
+    );
+
+    // only whitelisted tokens can be deposited
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: burner.to_string(),
+        amount: Uint128::new(1_000),
+        msg: to_json_binary(&Cw20HookMsg::DepositRewards {}).unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("random_token", &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotInWhitelist {});
+}
+
+// 5h. reward burners with a native denom
+#[test]
+fn burn_with_native_rewards() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+
+    // burnable denoms cannot be rewards
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RewardDenomBurnable {
+            denom: "uluna".to_string()
+        }
+    );
+
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetBurnableDenom {
+        denom_info: BurnableDenomInfo {
+            denom: "uusd".to_string(),
+            fee_ratio: None,
+            reward_weight: Decimal::one(),
+        },
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RewardDenomBurnable {
+            denom: "uusd".to_string()
+        }
+    );
+
+    // fund the pool with native coins
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("funder", &[coin(1_000, "ukrw")]),
+        ExecuteMsg::DepositRewards {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotInWhitelist {});
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("funder", &[coin(1_000, "uusd")]),
+        ExecuteMsg::DepositRewards {},
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000, "uusd")]);
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(5_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: "burner".to_string(),
+            amount: vec![coin(500, "uusd")],
+        })));
+
+    let reserves_query = query(deps.as_ref(), env, QueryMsg::RewardReserves {}).unwrap();
+    let reserves: RewardReservesResponse = from_json(&reserves_query).unwrap();
+    assert_eq!(reserves.reserves[0].deposited, Uint128::new(1_000));
+    assert_eq!(reserves.reserves[0].paid_out, Uint128::new(500));
+}
+
+// 5i. split the development fee between weighted beneficiaries
+#[test]
+fn split_development_fee_by_weight() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::SetFeeBeneficiaries {
+        beneficiaries: vec![
+            FeeBeneficiaryInfo {
+                address: "team".to_string(),
+                weight: 2,
+            },
+            FeeBeneficiaryInfo {
+                address: "team".to_string(),
+                weight: 1,
+            },
+        ],
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFeeBeneficiaries {});
+
+    let msg = ExecuteMsg::SetFeeBeneficiaries {
+        beneficiaries: vec![
+            FeeBeneficiaryInfo {
+                address: "team".to_string(),
+                weight: 2,
+            },
+            FeeBeneficiaryInfo {
+                address: "marketing".to_string(),
+                weight: 1,
+            },
+        ],
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(1_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "team".to_string(),
+            amount: vec![coin(66, "uluna")],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "marketing".to_string(),
+            amount: vec![coin(34, "uluna")],
+        })
+    );
+
+    // the deprecated development config only reports the first beneficiary
+    let development_config_query =
+        query(deps.as_ref(), env.clone(), QueryMsg::DevelopmentConfig {}).unwrap();
+    let development_config_res: DevelopmentConfigResponse =
+        from_json(&development_config_query).unwrap();
+    assert_eq!(development_config_res.0.beneficiary, "team");
+
+    // a single beneficiary from the old config gets weight 1
+    let msg = ExecuteMsg::UpdateDevelopmentConfig {
+        beneficiary: Some("audit".to_string()),
+        fee_ratio: None,
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let beneficiaries_query = query(deps.as_ref(), env, QueryMsg::FeeBeneficiaries {}).unwrap();
+    let beneficiaries: FeeBeneficiariesResponse = from_json(&beneficiaries_query).unwrap();
+    assert_eq!(beneficiaries.fee_ratio, Decimal::percent(10));
+    assert_eq!(
+        beneficiaries.beneficiaries,
+        vec![FeeBeneficiary {
+            address: Addr::unchecked("audit"),
+            weight: 1,
+        }]
+    );
+
+    // an empty beneficiary list is reported instead of panicking
+    DEVELOPMENT_FEE_BENEFICIARIES
+        .save(deps.as_mut().storage, &vec![])
+        .unwrap();
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::DevelopmentConfig {}).unwrap_err();
+    assert_eq!(err, StdError::generic_err("No fee beneficiaries"));
+}
+
+// 5j. multiply rewards by the reached reward tier
+#[test]
+fn burn_with_reward_tiers() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: uusd.clone(),
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "uusd")]);
+
+    let tier = |min_amount: u128, multiplier: Decimal| RewardTier {
+        min_amount: Uint128::new(min_amount),
+        multiplier,
+    };
+    let msg = ExecuteMsg::SetRewardTiers {
+        basis: RewardTierBasis::Cumulative,
+        tiers: vec![
+            tier(10_000, Decimal::percent(125)),
+            tier(1_000, Decimal::percent(110)),
+        ],
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidRewardTiers {});
+    let msg = ExecuteMsg::SetRewardTiers {
+        basis: RewardTierBasis::Cumulative,
+        tiers: vec![
+            tier(1_000, Decimal::percent(110)),
+            tier(10_000, Decimal::percent(125)),
+        ],
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // below the first tier
+    let simulate_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SimulateBurn {
+            denom: "uluna".to_string(),
+            amount: Uint128::new(500),
+            burner: Some("burner".to_string()),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    let simulate_res: SimulateBurnResponse = from_json(&simulate_query).unwrap();
+    assert_eq!(simulate_res.multiplier, Decimal::one());
+    assert_eq!(simulate_res.rewards[0].amount, Uint128::new(50));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(2_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: "burner".to_string(),
+            amount: vec![coin(220, "uusd")],
+        })));
+
+    // the earlier burn counts towards the top tier
+    let simulate_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::SimulateBurn {
+            denom: "uluna".to_string(),
+            amount: Uint128::new(9_000),
+            burner: Some("burner".to_string()),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    let simulate_res: SimulateBurnResponse = from_json(&simulate_query).unwrap();
+    assert_eq!(
+        simulate_res,
+        SimulateBurnResponse {
+            development_fee: Uint128::zero(),
+            burn_amount: Uint128::new(9_000),
+            multiplier: Decimal::percent(125),
+            rewards: vec![BurnerReward {
+                asset_info: uusd,
+                amount: Uint128::new(1_125),
+            }],
+            referral_rewards: vec![],
+            epoch_mode: false,
+        }
+    );
+}
+
+// 5k. pay referrers a share of referred burns
+#[test]
+fn burn_with_referrer() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "uusd")]);
+
+    let burn = ExecuteMsg::Burn {
+        referrer: Some("referrer".to_string()),
+        recipient: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("referrer", &[coin(1_000, "uluna")]),
+        burn.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SelfReferral {});
+
+    // referred volume is tracked before payouts are configured
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(1_000, "uluna")]),
+        burn.clone(),
+    )
+    .unwrap();
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: "burner".to_string(),
+            amount: vec![coin(90, "uusd")],
+        })));
+
+    let msg = ExecuteMsg::SetReferralConfig {
+        referral_config: Some(ReferralConfig {
+            source: ReferralSource::Rewards,
+            share: Decimal::zero(),
+        }),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidReferralShare {});
+    let msg = ExecuteMsg::SetReferralConfig {
+        referral_config: Some(ReferralConfig {
+            source: ReferralSource::Rewards,
+            share: Decimal::percent(20),
+        }),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(1_000, "uluna")]),
+        burn.clone(),
+    )
+    .unwrap();
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: "referrer".to_string(),
+            amount: vec![coin(18, "uusd")],
+        })));
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: "burner".to_string(),
+            amount: vec![coin(72, "uusd")],
+        })));
+
+    // the simulation applies the same referral split
+    let simulate = |deps: Deps| {
+        let simulate_query = query(
+            deps,
+            mock_env(),
+            QueryMsg::SimulateBurn {
+                denom: "uluna".to_string(),
+                amount: Uint128::new(1_000),
+                burner: Some("burner".to_string()),
+                referrer: Some("referrer".to_string()),
+            },
+        )
+        .unwrap();
+        from_json::<SimulateBurnResponse>(&simulate_query).unwrap()
+    };
+    let simulate_res = simulate(deps.as_ref());
+    assert_eq!(
+        simulate_res.rewards,
+        vec![BurnerReward {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(72),
+        }]
+    );
+    assert_eq!(
+        simulate_res.referral_rewards,
+        vec![BurnerReward {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(18),
+        }]
+    );
+
+    let msg = ExecuteMsg::SetReferralConfig {
+        referral_config: Some(ReferralConfig {
+            source: ReferralSource::DevelopmentFee,
+            share: Decimal::percent(50),
+        }),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let simulate_res = simulate(deps.as_ref());
+    assert_eq!(simulate_res.rewards[0].amount, Uint128::new(90));
+    assert_eq!(
+        simulate_res.referral_rewards,
+        vec![BurnerReward {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+            amount: Uint128::new(50),
+        }]
+    );
+
+    // the simulation refuses what a burn would refuse
+    let simulate_query = |denom: &str, referrer: &str| QueryMsg::SimulateBurn {
+        denom: denom.to_string(),
+        amount: Uint128::new(1_000),
+        burner: Some("referrer".to_string()),
+        referrer: Some(referrer.to_string()),
+    };
+    let err = query(
+        deps.as_ref(),
+        env.clone(),
+        simulate_query("uluna", "referrer"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(ContractError::SelfReferral {}.to_string())
+    );
+    let err = query(deps.as_ref(), env.clone(), simulate_query("uusd", "burner")).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            ContractError::NotBurnableDenom {
+                denom: "uusd".to_string()
+            }
+            .to_string()
+        )
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(1_000, "uluna")]),
+        burn,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "beneficiary".to_string(),
+            amount: vec![coin(50, "uluna")],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "referrer".to_string(),
+            amount: vec![coin(50, "uluna")],
+        })
+    );
+
+    let stats_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::ReferrerStats {
+            address: "referrer".to_string(),
+        },
+    )
+    .unwrap();
+    let stats: ReferrerStatsResponse = from_json(&stats_query).unwrap();
+    assert_eq!(
+        stats,
+        ReferrerStatsResponse {
+            address: Addr::unchecked("referrer"),
+            referred_amount: Uint128::new(2_700),
+            referral_count: 3,
+            earnings: vec![
+                BurnerReward {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: "uusd".to_string(),
+                    },
+                    amount: Uint128::new(18),
+                },
+                BurnerReward {
+                    asset_info: AssetInfo::NativeToken {
+                        denom: "uluna".to_string(),
+                    },
+                    amount: Uint128::new(50),
+                },
+            ],
+        }
+    );
+}
+
+// 5l. batch burns into an epoch and share its rewards pro rata
+#[test]
+fn burn_in_epochs() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: uusd.clone(),
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "uusd")]);
+
+    let msg = ExecuteMsg::SetEpochDuration { duration: Some(0) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::ZeroEpochDuration {});
+    let msg = ExecuteMsg::SetEpochDuration {
+        duration: Some(3_600),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // rewards are unknown until the epoch is processed
+    let simulate_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SimulateBurn {
+            denom: "uluna".to_string(),
+            amount: Uint128::new(2_000),
+            burner: Some("alice".to_string()),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    let simulate_res: SimulateBurnResponse = from_json(&simulate_query).unwrap();
+    assert!(simulate_res.epoch_mode);
+    assert!(simulate_res.rewards.is_empty());
+
+    // deposits are recorded instead of burned
+    for (burner, amount) in [("alice", 2_000), ("bob", 6_000)] {
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(burner, &[coin(amount, "uluna")]),
+            ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+    }
+
+    let share_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::EpochShare {
+            address: "alice".to_string(),
+            id: None,
+        },
+    )
+    .unwrap();
+    let share: EpochShareResponse = from_json(&share_query).unwrap();
+    assert_eq!(share.id, 1);
+    assert_eq!(share.weight, Uint128::new(1_799));
+    assert_eq!(share.share, Decimal::percent(25));
+    assert!(share.rewards.is_empty());
+
+    // depositors are only ranked once their deposit is burned and claimed
+    let top_burners_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TopBurners {
+            limit: None,
+            start_after: None,
+        },
+    )
+    .unwrap();
+    let top_burners: TopBurnersResponse = from_json(&top_burners_query).unwrap();
+    assert!(top_burners.burners.is_empty());
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("cranker", &[]),
+        ExecuteMsg::ProcessEpoch {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::EpochNotEnded {
+            end_time: env.block.time.seconds() + 3_600
+        }
+    );
+
+    env.block.time = env.block.time.plus_seconds(3_600);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("cranker", &[]),
+        ExecuteMsg::ProcessEpoch {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "beneficiary".to_string(),
+            amount: vec![coin(800, "uluna")],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(7_196, "uluna")],
+        })
+    );
+
+    let epoch_query = query(deps.as_ref(), env.clone(), QueryMsg::Epoch { id: Some(1) }).unwrap();
+    let epoch: EpochResponse = from_json(&epoch_query).unwrap();
+    assert!(epoch.epoch.processed);
+    assert_eq!(epoch.epoch.depositor_count, 2);
+    assert_eq!(
+        epoch.epoch.rewards,
+        vec![BurnerReward {
+            asset_info: uusd.clone(),
+            amount: Uint128::new(719),
+        }]
+    );
+    let epoch_query = query(deps.as_ref(), env.clone(), QueryMsg::Epoch { id: None }).unwrap();
+    let epoch: EpochResponse = from_json(&epoch_query).unwrap();
+    assert_eq!(epoch.epoch.id, 2);
+    assert_eq!(epoch.end_time, Some(env.block.time.seconds() + 3_600));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        ExecuteMsg::ClaimEpochRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: vec![coin(539, "uusd")],
+        })
+    );
+    let burner_info_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BurnerInfo {
+            address: "bob".to_string(),
+        },
+    )
+    .unwrap();
+    let burner_info: BurnerInfoResponse = from_json(&burner_info_query).unwrap();
+    assert_eq!(burner_info.burned_amount, Uint128::new(5_397));
+    let history_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BurnHistory {
+            start_after: None,
+            limit: None,
+            burner: Some("bob".to_string()),
+        },
+    )
+    .unwrap();
+    let history_res: BurnHistoryResponse = from_json(&history_query).unwrap();
+    assert_eq!(history_res.records.len(), 1);
+    assert_eq!(history_res.records[0].burned, vec![coin(5_397, "uluna")]);
+    assert_eq!(
+        history_res.records[0].development_fee,
+        vec![coin(600, "uluna")]
+    );
+    assert_eq!(
+        history_res.records[0].rewards,
+        vec![BurnerReward {
+            asset_info: uusd.clone(),
+            amount: Uint128::new(539),
+        }]
+    );
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("bob", &[]),
+        ExecuteMsg::ClaimEpochRewards {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+
+    // unclaimed epoch rewards stay reserved
+    let reserves_query = query(deps.as_ref(), env, QueryMsg::RewardReserves {}).unwrap();
+    let reserves: RewardReservesResponse = from_json(&reserves_query).unwrap();
+    assert_eq!(reserves.reserves[0].reserved, Uint128::new(180));
+}
+
+// 5m. burn an allowlisted cw20 token directly
+#[test]
+fn burn_cw20_token() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "uusd")]);
+
+    let burn_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "burner".to_string(),
+        amount: Uint128::new(1_000),
+        msg: to_json_binary(&Cw20HookMsg::Burn {
+            referrer: None,
+            recipient: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("cremation", &[]),
+        burn_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NotBurnableToken {
+            token: "cremation".to_string()
+        }
+    );
+
+    let msg = ExecuteMsg::SetBurnableToken {
+        token_info: BurnableTokenInfo {
+            token: "cremation".to_string(),
+            fee_ratio: None,
+            reward_weight: Decimal::one(),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // burnable tokens cannot be rewards
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::Token {
+                contract_addr: "cremation".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::RewardTokenBurnable {
+            token: "cremation".to_string()
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("cremation", &[]),
+        burn_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cremation".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Transfer {
+                recipient: "beneficiary".to_string(),
+                amount: Uint128::new(100),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "cremation".to_string(),
+            msg: to_json_binary(&cw20::Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(900),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "burner".to_string(),
+            amount: vec![coin(90, "uusd")],
+        })
+    );
+
+    let burned_query = query(deps.as_ref(), env.clone(), QueryMsg::BurnedTokens {}).unwrap();
+    let burned: BurnedTokensResponse = from_json(&burned_query).unwrap();
+    assert_eq!(
+        burned.burned_tokens,
+        vec![BurnedToken {
+            token: Addr::unchecked("cremation"),
+            amount: Uint128::new(900),
+        }]
+    );
+
+    // the token burn is recorded in the burn history
+    let history_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BurnHistory {
+            start_after: None,
+            limit: None,
+            burner: Some("burner".to_string()),
+        },
+    )
+    .unwrap();
+    let history_res: BurnHistoryResponse = from_json(&history_query).unwrap();
+    assert_eq!(history_res.records.len(), 1);
+    assert_eq!(
+        history_res.records[0].token_burn,
+        Some(TokenBurn {
+            token: Addr::unchecked("cremation"),
+            burned: Uint128::new(900),
+            development_fee: Uint128::new(100),
+        })
+    );
+    assert!(history_res.records[0].burned.is_empty());
+    assert_eq!(
+        history_res.records[0].rewards,
+        vec![BurnerReward {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            amount: Uint128::new(90),
+        }]
+    );
+
+    // no uluna was burned, so the burner is not ranked
+    let top_burners_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::TopBurners {
+            limit: None,
+            start_after: None,
+        },
+    )
+    .unwrap();
+    let top_burners: TopBurnersResponse = from_json(&top_burners_query).unwrap();
+    assert!(top_burners.burners.is_empty());
+
+    // the stats count the burn with its token amounts apart from burned uluna
+    let day = env.block.time.seconds() / 86_400;
+    let stats_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::BurnStats {
+            from_day: day,
+            to_day: day,
+        },
+    )
+    .unwrap();
+    let stats: BurnStatsResponse = from_json(&stats_query).unwrap();
+    assert_eq!(stats.days[0].stats.burn_count, 1);
+    assert!(stats.days[0].stats.burned_luna.is_zero());
+    assert_eq!(
+        stats.days[0].stats.token_burns,
+        vec![TokenBurn {
+            token: Addr::unchecked("cremation"),
+            burned: Uint128::new(900),
+            development_fee: Uint128::new(100),
+        }]
+    );
+}
+
+// 5n. pay referrers of epoch deposits their fee share on deposit and reward share on claim
+#[test]
+fn refer_epoch_deposits() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: uusd.clone(),
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "uusd")]);
+    let msg = ExecuteMsg::SetEpochDuration {
+        duration: Some(3_600),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetReferralConfig {
+        referral_config: Some(ReferralConfig {
+            source: ReferralSource::Rewards,
+            share: Decimal::percent(20),
+        }),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    for (burner, amount, referrer) in [
+        ("alice", 2_000, Some("referrer".to_string())),
+        ("bob", 6_000, None),
+    ] {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(burner, &[coin(amount, "uluna")]),
+            ExecuteMsg::Burn {
+                referrer,
+                recipient: None,
+            },
+        )
+        .unwrap();
+    }
+
+    // deposits are not counted as burned before the epoch is processed
+    let day = env.block.time.seconds() / 86_400;
+    let stats_query = |deps: Deps, env: Env| {
+        let stats_query = query(
+            deps,
+            env,
+            QueryMsg::BurnStats {
+                from_day: day,
+                to_day: day,
+            },
+        )
+        .unwrap();
+        from_json::<BurnStatsResponse>(&stats_query).unwrap()
+    };
+    assert!(stats_query(deps.as_ref(), env.clone()).days.is_empty());
+
+    env.block.time = env.block.time.plus_seconds(3_600);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("cranker", &[]),
+        ExecuteMsg::ProcessEpoch {},
+    )
+    .unwrap();
+    let stats = stats_query(deps.as_ref(), env.clone());
+    assert_eq!(stats.days[0].stats.burned_luna, Uint128::new(7_196));
+    assert_eq!(stats.days[0].stats.burn_count, 0);
+
+    // the referrer takes its share of alice's rewards
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[]),
+        ExecuteMsg::ClaimEpochRewards {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: vec![coin(144, "uusd")],
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referrer".to_string(),
+                amount: vec![coin(35, "uusd")],
+            }),
+        ]
+    );
+    let stats = stats_query(deps.as_ref(), env.clone());
+    assert_eq!(stats.days[0].stats.burn_count, 1);
+    assert_eq!(stats.days[0].stats.burner_count, 1);
+
+    let stats_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::ReferrerStats {
+            address: "referrer".to_string(),
+        },
+    )
+    .unwrap();
+    let stats: ReferrerStatsResponse = from_json(&stats_query).unwrap();
+    assert_eq!(stats.referred_amount, Uint128::new(1_799));
+    assert_eq!(stats.referral_count, 1);
+    assert_eq!(
+        stats.earnings,
+        vec![BurnerReward {
+            asset_info: uusd,
+            amount: Uint128::new(35),
+        }]
+    );
+
+    // a development fee share is paid with the deposit and left out of the beneficiary's fee
+    let msg = ExecuteMsg::SetReferralConfig {
+        referral_config: Some(ReferralConfig {
+            source: ReferralSource::DevelopmentFee,
+            share: Decimal::percent(50),
+        }),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("carol", &[coin(2_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: Some("referrer".to_string()),
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "referrer".to_string(),
+            amount: vec![coin(100, "uluna")],
+        })
+    );
+    env.block.time = env.block.time.plus_seconds(3_600);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("cranker", &[]),
+        ExecuteMsg::ProcessEpoch {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "beneficiary".to_string(),
+            amount: vec![coin(100, "uluna")],
+        })
+    );
+}
+
+// ============= burner leaderboard =============
+// 6a. rank burners by burned amount
+#[test]
+fn rank_top_burners() {
+    let owner = "owner";
+    let burners = ["burner1", "burner2", "burner3"];
+    let burn_amounts = [3_000u128, 10_000, 5_000];
+
+    let mut app = App::new(|router, _, storage| {
+        for burner in burners {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(burner),
+                    vec![coin(1_000_000, "uluna")],
+                )
+                .unwrap();
+        }
+    });
+    let setup_res = helpers::setup_multi_test_contracts(&mut app, owner, 1_000_000_000, None);
+    let burning_addr = setup_res.burning_addr;
+    let reward_list = setup_res.reward_list;
+
+    for (burner, burn_amount) in burners.iter().zip(burn_amounts) {
+        app.execute_contract(
+            Addr::unchecked(*burner),
+            burning_addr.clone(),
+            &ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
+            &[coin(burn_amount, "uluna")],
+        )
+        .unwrap();
+    }
+
+    let burner_info: BurnerInfoResponse = app
+        .wrap()
+        .query_wasm_smart(
+            burning_addr.clone(),
+            &QueryMsg::BurnerInfo {
+                address: "burner2".to_string(),
+            },
+        )
+        .unwrap();
+    let development_fee = Uint128::new(10_000) * Decimal::percent(2);
+    let burned_amount = Uint128::new(10_000) - development_fee - development_fee * LUNC_TAX;
+    assert_eq!(burner_info.burned_amount, burned_amount);
+    assert_eq!(burner_info.rewards.len(), reward_list.len());
+    assert_eq!(
+        burner_info.rewards[0].amount,
+        burned_amount * reward_list[0].reward_ratio
+    );
+
+    let top_burners: TopBurnersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            burning_addr.clone(),
+            &QueryMsg::TopBurners {
+                limit: Some(2),
+                start_after: None,
+            },
+        )
+        .unwrap();
+    let top_addresses = top_burners
+        .burners
+        .iter()
+        .map(|burner| burner.address.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(top_addresses, vec!["burner2", "burner3"]);
+
+    let top_burners: TopBurnersResponse = app
+        .wrap()
+        .query_wasm_smart(
+            burning_addr,
+            &QueryMsg::TopBurners {
+                limit: Some(2),
+                start_after: Some("burner3".to_string()),
+            },
+        )
+        .unwrap();
+    assert_eq!(top_burners.burners.len(), 1);
+    assert_eq!(top_burners.burners[0].address, "burner1");
+}
+
+// 6b. credit swap and burn to the cached burner
+#[test]
+fn record_swap_and_burn_burner() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        development_config: DevelopmentConfig {
+            fee_ratio: Decimal::zero(),
+            beneficiary: "beneficiary".to_string(),
+        },
+    };
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        init_msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }],
+        denom: "uusd".to_string(),
+        minimum_receive: None,
+        deadline: None,
+        referrer: None,
+        recipient: None,
+    };
+    let info = mock_info("swapper", &[coin(1000, "uusd")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uluna")]);
+    let reply_msg = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+
+    let burner_info_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::BurnerInfo {
+            address: "swapper".to_string(),
+        },
+    )
+    .unwrap();
+    let burner_info: BurnerInfoResponse = from_json(&burner_info_query).unwrap();
+    assert_eq!(burner_info.burned_amount, Uint128::new(500));
+}
+
+// 6c. credit burns on behalf of a recipient to the recipient
+#[test]
+fn record_burn_for_recipient() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        development_config: DevelopmentConfig {
+            fee_ratio: Decimal::zero(),
+            beneficiary: "beneficiary".to_string(),
+        },
+    };
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        init_msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "ukrw".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "ukrw")]);
+
+    // the recipient cannot also be the referrer
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("aggregator", &[coin(1_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: Some("user".to_string()),
+            recipient: Some("user".to_string()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SelfReferral {});
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("aggregator", &[coin(1_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: Some("user".to_string()),
+        },
+    )
+    .unwrap();
+    assert!(res.messages.iter().any(|msg| msg.msg
+        == CosmosMsg::Bank(BankMsg::Send {
+            to_address: "user".to_string(),
+            amount: vec![coin(100, "ukrw")],
+        })));
+
+    let msg = ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }],
+        denom: "uusd".to_string(),
+        minimum_receive: None,
+        deadline: None,
+        referrer: None,
+        recipient: Some("user".to_string()),
+    };
+    let info = mock_info("aggregator", &[coin(1000, "uusd")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    deps.querier.update_balance(
+        env.contract.address.clone(),
+        vec![coin(500, "uluna"), coin(1_000_000, "ukrw")],
+    );
+    let reply_msg = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+
+    let top_burners_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::TopBurners {
+            limit: None,
+            start_after: None,
+        },
+    )
+    .unwrap();
+    let top_burners: TopBurnersResponse = from_json(&top_burners_query).unwrap();
+    assert_eq!(top_burners.burners.len(), 1);
+    assert_eq!(top_burners.burners[0].address, Addr::unchecked("user"));
+    assert_eq!(top_burners.burners[0].burned_amount, Uint128::new(1_500));
+}
+
+// ============= burn history =============
+// 7a. record every burn
+#[test]
+fn record_burn_history() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let owner = "owner";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        development_config: DevelopmentConfig {
+            fee_ratio: Decimal::percent(2),
+            beneficiary: "beneficiary".to_string(),
+        },
+    };
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        init_msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    for burner in ["burner1", "burner2", "burner1"] {
+        let info = mock_info(burner, &[coin(1000, "uluna")]);
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::Burn {
+                referrer: None,
+                recipient: None,
+            },
+        )
+        .unwrap();
+        env.block.time = env.block.time.plus_seconds(10);
+    }
+
+    // swap and burn records the swapped asset
+    let msg = ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }],
+        denom: "uusd".to_string(),
+        minimum_receive: None,
+        deadline: None,
+        referrer: None,
+        recipient: None,
+    };
+    let info = mock_info("burner2", &[coin(2000, "uusd")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uluna")]);
+    let reply_msg = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+
+    let history_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BurnHistory {
+            start_after: None,
+            limit: None,
+            burner: None,
+        },
+    )
+    .unwrap();
+    let history_res: BurnHistoryResponse = from_json(&history_query).unwrap();
+    assert_eq!(history_res.records.len(), 4);
+    assert_eq!(history_res.records[0].burned, vec![coin(980, "uluna")]);
+    assert_eq!(
+        history_res.records[0].development_fee,
+        vec![coin(20, "uluna")]
+    );
+    assert_eq!(history_res.records[0].swap_input, None);
+    assert_eq!(
+        history_res.records[1].time,
+        history_res.records[0].time + 10
+    );
+    assert_eq!(
+        history_res.records[3].swap_input,
+        Some(SwapInput {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string()
+            },
+            amount: Uint128::new(2000),
+        })
+    );
+
+    // burns of a burner, paginated by record id
+    let history_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::BurnHistory {
+            start_after: Some(2),
+            limit: Some(10),
+            burner: Some("burner2".to_string()),
+        },
+    )
+    .unwrap();
+    let history_res: BurnHistoryResponse = from_json(&history_query).unwrap();
+    let record_ids = history_res
+        .records
+        .iter()
+        .map(|record| record.id)
+        .collect::<Vec<_>>();
+    assert_eq!(record_ids, vec![4]);
+}
+
+// 7b. bucket burn stats by day and week
+#[test]
+fn record_daily_burn_stats() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: uusd.clone(),
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000_000, "uusd")]);
+
+    let burn = ExecuteMsg::Burn {
+        referrer: None,
+        recipient: None,
+    };
+    let first_day = env.block.time.seconds() / 86_400;
+    for (burner, amount) in [("alice", 1_000), ("alice", 1_000), ("bob", 2_000)] {
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(burner, &[coin(amount, "uluna")]),
+            burn.clone(),
+        )
+        .unwrap();
+    }
+    env.block.time = env.block.time.plus_seconds(86_400);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("alice", &[coin(1_000, "uluna")]),
+        burn,
+    )
+    .unwrap();
+
+    let stats_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BurnStats {
+            from_day: first_day,
+            to_day: first_day + 1,
+        },
+    )
+    .unwrap();
+    let stats: BurnStatsResponse = from_json(&stats_query).unwrap();
+    assert_eq!(
+        stats.days,
+        vec![
+            BurnStatsBucket {
+                id: first_day,
+                stats: BurnStats {
+                    burned_luna: Uint128::new(3_599),
+                    burn_count: 3,
+                    burner_count: 2,
+                    development_fee: vec![coin(400, "uluna")],
+                    rewards: vec![BurnerReward {
+                        asset_info: uusd.clone(),
+                        amount: Uint128::new(359),
+                    }],
+                    token_burns: vec![],
+                },
+            },
+            BurnStatsBucket {
+                id: first_day + 1,
+                stats: BurnStats {
+                    burned_luna: Uint128::new(900),
+                    burn_count: 1,
+                    burner_count: 1,
+                    development_fee: vec![coin(100, "uluna")],
+                    rewards: vec![BurnerReward {
+                        asset_info: uusd,
+                        amount: Uint128::new(90),
+                    }],
+                    token_burns: vec![],
+                },
+            },
+        ]
+    );
+    let burned_luna = stats
+        .weeks
+        .iter()
+        .map(|week| week.stats.burned_luna)
+        .sum::<Uint128>();
+    assert_eq!(burned_luna, Uint128::new(4_499));
+    let burn_count = stats
+        .weeks
+        .iter()
+        .map(|week| week.stats.burn_count)
+        .sum::<u64>();
+    assert_eq!(burn_count, 4);
+
+    let err = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::BurnStats {
+            from_day: first_day + 1,
+            to_day: first_day,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("from_day is after to_day"));
+}
+
+// ============= swap and burn =============
+// 8a. pass slippage protection to the router and check it in reply
+#[test]
+fn swap_and_burn_with_minimum_receive() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        development_config: DevelopmentConfig {
+            fee_ratio: Decimal::percent(2),
+            beneficiary: "beneficiary".to_string(),
+        },
+    };
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        init_msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let deadline = env.block.time.seconds() + 60;
+    let msg = ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }],
+        denom: "uusd".to_string(),
+        minimum_receive: Some(Uint128::new(600)),
+        deadline: Some(deadline),
+        referrer: None,
+        recipient: None,
+    };
+    let info = mock_info("swapper", &[coin(1000, "uusd")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let swap_msg = match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => msg,
+        _ => panic!("Unexpected message"),
+    };
+    match from_json(swap_msg).unwrap() {
+        RouterExecuteMsg::ExecuteSwapOperations {
+            minimum_receive,
+            deadline: swap_deadline,
+            ..
+        } => {
+            assert_eq!(minimum_receive, Some(Uint128::new(600)));
+            assert_eq!(swap_deadline, Some(deadline));
+        }
+    }
+
+    // fail when the swap returns less than the minimum
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uluna")]);
+    let reply_msg = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let err = reply(deps.as_mut(), env.clone(), reply_msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MinimumReceiveNotMet {
+            received: Uint128::new(500),
+            minimum_receive: Uint128::new(600),
+        }
+    );
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(700, "uluna")]);
+    reply(deps.as_mut(), env, reply_msg).unwrap();
+}
+
+// 8b. route each hop through its own dex
+#[test]
+fn swap_and_burn_with_dex_per_hop() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        development_config: DevelopmentConfig {
+            fee_ratio: Decimal::zero(),
+            beneficiary: "beneficiary".to_string(),
+        },
+    };
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        init_msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Loop,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let uluna = AssetInfo::NativeToken {
+        denom: "uluna".to_string(),
+    };
+    let swap_paths = vec![
+        SwapHop {
+            dex: SwapDex::Loop,
+            ask_asset_info: uusd.clone(),
+        },
+        SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: uluna.clone(),
+        },
+    ];
+    let receive_msg = |swap_paths: Vec<SwapHop>| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "swapper".to_string(),
+            amount: Uint128::new(1000),
+            msg: to_json_binary(&Cw20HookMsg::SwapAndBurn {
+                swap_paths,
+                minimum_receive: None,
+                deadline: None,
+                referrer: None,
+                recipient: None,
+            })
+            .unwrap(),
+        })
+    };
+
+    // the native router is not set yet
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("token", &[]),
+        receive_msg(swap_paths.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SwapRouterNotSet {
+            dex: "native".to_string()
+        }
+    );
+
+    // the last hop must end in uluna
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("token", &[]),
+        receive_msg(swap_paths[..1].to_vec()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidSwapPaths {});
+
+    // hops through the same router are sent in one swap
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let swap_router_query = query(deps.as_ref(), env.clone(), QueryMsg::SwapRouter {}).unwrap();
+    let swap_router_res: SwapRouterResponse = from_json(&swap_router_query).unwrap();
+    assert_eq!(
+        swap_router_res
+            .routers
+            .iter()
+            .map(|router| router.dex.clone())
+            .collect::<Vec<_>>(),
+        vec![SwapDex::Loop, SwapDex::Native]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("token", &[]),
+        receive_msg(swap_paths),
+    )
+    .unwrap();
+    let swap_msg = match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_json(msg).unwrap() {
+            cw20::Cw20ExecuteMsg::Send { contract, msg, .. } => {
+                assert_eq!(contract, "router");
+                msg
+            }
+            _ => panic!("Unexpected cw20 message"),
+        },
+        _ => panic!("Unexpected message"),
+    };
+    match from_json(&swap_msg).unwrap() {
+        RouterExecuteMsg::ExecuteSwapOperations { operations, .. } => assert_eq!(
+            operations,
+            vec![
+                SwapOperation::Loop {
+                    offer_asset_info: AssetInfo::Token {
+                        contract_addr: "token".to_string(),
+                    },
+                    ask_asset_info: uusd,
+                },
+                SwapOperation::NativeSwap {
+                    offer_denom: "uusd".to_string(),
+                    ask_denom: "uluna".to_string(),
+                },
+            ]
+        ),
+    }
+}
+
+// 8c. chain a swap through each router and burn only the output of the last one
+#[test]
+fn swap_and_burn_through_chained_routers() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    for (dex, router) in [
+        (SwapDex::Terraswap, "router"),
+        (SwapDex::Native, "native_router"),
+    ] {
+        let msg = ExecuteMsg::SetSwapRouter {
+            dex,
+            router: router.to_string(),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    }
+
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let msg = ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![
+            SwapHop {
+                dex: SwapDex::Terraswap,
+                ask_asset_info: uusd.clone(),
+            },
+            SwapHop {
+                dex: SwapDex::Native,
+                ask_asset_info: AssetInfo::NativeToken {
+                    denom: "uluna".to_string(),
+                },
+            },
+        ],
+        denom: "ukrw".to_string(),
+        minimum_receive: Some(Uint128::new(400)),
+        deadline: Some(100),
+        referrer: None,
+        recipient: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("swapper", &[coin(1000, "ukrw")]),
+        msg,
+    )
+    .unwrap();
+    let swap_id = res.messages[0].id;
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: vec![SwapOperation::TerraSwap {
+                    offer_asset_info: AssetInfo::NativeToken {
+                        denom: "ukrw".to_string(),
+                    },
+                    ask_asset_info: uusd.clone(),
+                }],
+                minimum_receive: None,
+                to: None,
+                deadline: Some(100),
+            })
+            .unwrap(),
+            funds: vec![coin(995, "ukrw")],
+        })
+    );
+
+    // the uusd of the first run is swapped through the native router, minus its send tax
+    let swap_reply = Reply {
+        id: swap_id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(800, "uusd")]);
+    let res = reply(deps.as_mut(), env.clone(), swap_reply.clone()).unwrap();
+    assert_eq!(res.messages[0].id, swap_id);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "native_router".to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: vec![SwapOperation::NativeSwap {
+                    offer_denom: "uusd".to_string(),
+                    ask_denom: "uluna".to_string(),
+                }],
+                minimum_receive: Some(Uint128::new(400)),
+                to: None,
+                deadline: Some(100),
+            })
+            .unwrap(),
+            funds: vec![coin(796, "uusd")],
+        })
+    );
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uluna")]);
+    reply(deps.as_mut(), env.clone(), swap_reply).unwrap();
+    let burner_info_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::BurnerInfo {
+            address: "swapper".to_string(),
+        },
+    )
+    .unwrap();
+    let burner_info: BurnerInfoResponse = from_json(&burner_info_query).unwrap();
+    assert_eq!(burner_info.burned_amount, Uint128::new(500));
+}
+
+// 8d. burn only the swapped uluna and leave stray uluna to sweep
+#[test]
+fn swap_and_burn_ignores_stray_balance() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        development_config: DevelopmentConfig {
+            fee_ratio: Decimal::zero(),
+            beneficiary: "beneficiary".to_string(),
+        },
+    };
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        init_msg,
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // uluna sent to the contract by mistake
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(300, "uluna")]);
+
+    let msg = ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }],
+        denom: "uusd".to_string(),
+        minimum_receive: Some(Uint128::new(500)),
+        deadline: None,
+        referrer: None,
+        recipient: None,
+    };
+    let info = mock_info("swapper", &[coin(1000, "uusd")]);
+    let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(800, "uluna")]);
+    let reply_msg = Reply {
+        id: res.messages[0].id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = reply(deps.as_mut(), env.clone(), reply_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Burn {
+            amount: vec![coin(500, "uluna")],
+        })
+    );
+
+    let burner_info_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::BurnerInfo {
+            address: "swapper".to_string(),
+        },
+    )
+    .unwrap();
+    let burner_info: BurnerInfoResponse = from_json(&burner_info_query).unwrap();
+    assert_eq!(burner_info.burned_amount, Uint128::new(500));
+
+    // only the owner sweeps the stray balance
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(300, "uluna")]);
+    let msg = ExecuteMsg::Sweep {
+        denom: "uluna".to_string(),
+        recipient: "treasury".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("random", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), env, mock_info(owner, &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(300, "uluna")],
+        })
+    );
+}
+
+// 8e. resolve each reply to its own swap when swaps interleave
+#[test]
+fn swap_and_burn_interleaved() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetSwapRouter {
+        dex: SwapDex::Native,
+        router: "router".to_string(),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    let swap_and_burn = |recipient: &str| ExecuteMsg::SwapAndBurn {
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: AssetInfo::NativeToken {
+                denom: "uluna".to_string(),
+            },
+        }],
+        denom: "uusd".to_string(),
+        minimum_receive: None,
+        deadline: None,
+        referrer: None,
+        recipient: Some(recipient.to_string()),
+    };
+    let info = mock_info("batcher", &[coin(1000, "uusd")]);
+    let first_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        swap_and_burn("alice"),
+    )
+    .unwrap();
+    let second_res = execute(deps.as_mut(), env.clone(), info, swap_and_burn("bob")).unwrap();
+    assert_ne!(first_res.messages[0].id, second_res.messages[0].id);
+
+    let swap_reply = |id: u64| Reply {
+        id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(300, "uluna")]);
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        swap_reply(second_res.messages[0].id),
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uluna")]);
+    reply(
+        deps.as_mut(),
+        env.clone(),
+        swap_reply(first_res.messages[0].id),
+    )
+    .unwrap();
+
+    // a context is resolved only once
+    let err = reply(
+        deps.as_mut(),
+        env.clone(),
+        swap_reply(first_res.messages[0].id),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidReplyMsg {});
+
+    for (address, burned_amount) in [("alice", 500), ("bob", 300)] {
+        let burner_info_query = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::BurnerInfo {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+        let burner_info: BurnerInfoResponse = from_json(&burner_info_query).unwrap();
+        assert_eq!(burner_info.burned_amount, Uint128::new(burned_amount));
+    }
+}
+
+// 8f. keep the deposits of the open epoch out of a sweep
+#[test]
+fn sweep_during_open_epoch() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::percent(10),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::SetEpochDuration {
+        duration: Some(3_600),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+
+    // 900 uluna to burn and 100 uluna of development fee wait for the epoch
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(1_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_300, "uluna")]);
+
+    let msg = ExecuteMsg::Sweep {
+        denom: "uluna".to_string(),
+        recipient: "treasury".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(300, "uluna")],
+        })
+    );
+
+    // nothing is left once the stray uluna is gone
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000, "uluna")]);
+    let err = execute(deps.as_mut(), env, mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+}
+
+// 8g. keep vesting native rewards out of a sweep
+#[test]
+fn sweep_with_vesting_native_rewards() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    let msg = ExecuteMsg::UpdateRewardVesting {
+        duration: Some(100),
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000, "uusd")]);
+
+    // the 500 uusd reward is locked in the contract
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(5_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Sweep {
+        denom: "uusd".to_string(),
+        recipient: "treasury".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(500, "uusd")],
+        })
+    );
+
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uusd")]);
+    let err = execute(deps.as_mut(), env, mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+}
+
+// 8h. keep deposited native rewards not yet paid out of a sweep
+#[test]
+fn sweep_with_deposited_native_rewards() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("deployer", &[]),
+        InstantiateMsg {
+            owner: owner.to_string(),
+            development_config: DevelopmentConfig {
+                fee_ratio: Decimal::zero(),
+                beneficiary: "beneficiary".to_string(),
+            },
+        },
+    )
+    .unwrap();
+    let msg = ExecuteMsg::AddToRewardWhitelist {
+        reward_info: RewardInfo {
+            asset_info: AssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            reward_ratio: Decimal::percent(10),
+        },
+    };
+    execute(deps.as_mut(), env.clone(), mock_info(owner, &[]), msg).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("funder", &[coin(1_000, "uusd")]),
+        ExecuteMsg::DepositRewards {},
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_200, "uusd")]);
+
+    // only the 200 uusd sent outside of a deposit is stray
+    let msg = ExecuteMsg::Sweep {
+        denom: "uusd".to_string(),
+        recipient: "treasury".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![coin(200, "uusd")],
+        })
+    );
+
+    // a paid out reward releases its part of the deposit
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(1_000, "uusd")]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("burner", &[coin(5_000, "uluna")]),
+        ExecuteMsg::Burn {
+            referrer: None,
+            recipient: None,
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_balance(env.contract.address.clone(), vec![coin(500, "uusd")]);
+    let err = execute(deps.as_mut(), env, mock_info(owner, &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+}
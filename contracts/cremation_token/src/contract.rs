@@ -2,11 +2,12 @@ use crate::{msg::*, state::*};
 
 use classic_terraswap::asset::AssetInfo;
 use cosmwasm_std::{
-    attr, to_json_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+    attr, to_json_binary, to_json_vec, Addr, BankMsg, Binary, BlockInfo, Coin, Decimal, Deps,
+    DepsMut, Env, Event, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    Uint128, WasmMsg,
 };
 use cw2::set_contract_version;
-use cw20::{AllowanceResponse, Cw20ReceiveMsg};
+use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
 use cw20_base::{
     allowances::{
         deduct_allowance, execute_burn_from, execute_decrease_allowance,
@@ -28,6 +29,13 @@ const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub const SWAP_COLLECTED_TAX_THRESHOLD: Uint128 = Uint128::new(10_000 * 1_000_000);
 
+pub const BALANCE_HOOK_REPLY_ID: u64 = 1;
+// hooks run with a gas limit so that an out of gas error is caught by the reply
+// instead of aborting the whole transfer
+pub const DEFAULT_BALANCE_HOOK_GAS_LIMIT: u64 = 500_000;
+pub const BURN_COLLECTED_TAX_REPLY_ID: u64 = 2;
+pub const LUNC_TAX: Decimal = Decimal::permille(5);
+
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
@@ -81,6 +89,10 @@ pub fn execute(
         ExecuteMsg::UpdateCollectTaxAddress {
             new_collect_tax_addr,
         } => execute::update_collecting_tax_address(deps, env, info, new_collect_tax_addr),
+        ExecuteMsg::SetBurningContract { burning_contract } => {
+            execute::set_burning_contract(deps, env, info, burning_contract)
+        }
+        ExecuteMsg::Sweep { denom, recipient } => execute::sweep(deps, env, info, denom, recipient),
         ExecuteMsg::UpdateTaxInfo {
             buy_tax,
             sell_tax,
@@ -89,6 +101,49 @@ pub fn execute(
         ExecuteMsg::SetTaxFreeAddress { address, tax_free } => {
             execute::set_tax_free_address(deps, env, info, address, tax_free)
         }
+        ExecuteMsg::RegisterEcosystemContract { address, role } => {
+            execute::register_ecosystem_contract(deps, env, info, address, role)
+        }
+        ExecuteMsg::DeregisterEcosystemContract { address } => {
+            execute::deregister_ecosystem_contract(deps, env, info, address)
+        }
+        ExecuteMsg::SetPairTaxInfo {
+            pair_address,
+            tax_info,
+        } => execute::set_pair_tax_info(deps, env, info, pair_address, tax_info),
+        ExecuteMsg::SetSellLimit { sell_limit } => {
+            execute::set_sell_limit(deps, env, info, sell_limit)
+        }
+        ExecuteMsg::Permit {
+            owner,
+            spender,
+            amount,
+            expires,
+            nonce,
+            signature,
+            pubkey,
+        } => execute::permit(
+            deps, env, info, owner, spender, amount, expires, nonce, signature, pubkey,
+        ),
+        ExecuteMsg::CreateVesting {
+            address,
+            amount,
+            start_time,
+            cliff,
+            duration,
+            revocable,
+        } => execute::create_vesting(
+            deps, env, info, address, amount, start_time, cliff, duration, revocable,
+        ),
+        ExecuteMsg::RevokeVesting { address } => execute::revoke_vesting(deps, env, info, address),
+        ExecuteMsg::AddBalanceHook {
+            contract,
+            addresses,
+            gas_limit,
+        } => execute::add_balance_hook(deps, env, info, contract, addresses, gas_limit),
+        ExecuteMsg::RemoveBalanceHook { contract } => {
+            execute::remove_balance_hook(deps, env, info, contract)
+        }
 
         // ======= Existed executes from cw20-base =======
         ExecuteMsg::Send {
@@ -113,9 +168,11 @@ pub fn execute(
         ExecuteMsg::UpdateMinter { new_minter } => {
             execute_update_minter(deps, env, info, new_minter)
         }
-        ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient, amount),
-        ExecuteMsg::Burn { amount } => execute_burn(deps, env, info, amount),
-        ExecuteMsg::BurnFrom { owner, amount } => execute_burn_from(deps, env, info, owner, amount),
+        ExecuteMsg::Mint { recipient, amount } => execute::mint(deps, env, info, recipient, amount),
+        ExecuteMsg::Burn { amount } => execute::burn(deps, env, info, amount),
+        ExecuteMsg::BurnFrom { owner, amount } => {
+            execute::burn_from(deps, env, info, owner, amount)
+        }
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
@@ -135,7 +192,59 @@ pub fn execute(
     }
 }
 
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // a failing hook must not revert the balance change that triggered it
+        BALANCE_HOOK_REPLY_ID => {
+            let error = msg.result.unwrap_err();
+            let event = Event::new("cremation_balance_hook_failed").add_attribute("error", error);
+            Ok(Response::new().add_event(event))
+        }
+        BURN_COLLECTED_TAX_REPLY_ID => {
+            let pre_swap_balance = PRE_TAX_SWAP_BALANCE.load(deps.storage)?;
+            PRE_TAX_SWAP_BALANCE.remove(deps.storage);
+            let balance = deps
+                .querier
+                .query_balance(&env.contract.address, "uluna")?
+                .amount;
+            let swapped_amount = balance
+                .checked_sub(pre_swap_balance)
+                .map_err(StdError::from)?;
+            let burning_contract = BURNING_CONTRACT.load(deps.storage)?;
+            let collect_tax_address = COLLECT_TAX_ADDRESS.load(deps.storage)?;
+
+            // keep the send tax of the chain in this contract, what is left over can be swept
+            let burn_amount = swapped_amount - swapped_amount * LUNC_TAX;
+            let event = Event::new("cremation_burn_collected_tax").add_attributes(vec![
+                attr("burning_contract", &burning_contract),
+                attr("recipient", &collect_tax_address),
+                attr("swapped_amount", swapped_amount),
+                attr("burn_amount", burn_amount),
+            ]);
+            if burn_amount.is_zero() {
+                return Ok(Response::new().add_event(event));
+            }
+
+            let msg = WasmMsg::Execute {
+                contract_addr: burning_contract.to_string(),
+                // credit the burn to the tax collector rather than this contract
+                msg: to_json_binary(&BurningExecuteMsg::Burn {
+                    recipient: Some(collect_tax_address.to_string()),
+                })?,
+                funds: vec![Coin {
+                    denom: "uluna".to_string(),
+                    amount: burn_amount,
+                }],
+            };
+            Ok(Response::new().add_message(msg).add_event(event))
+        }
+        _ => Err(StdError::generic_err("Invalid reply id").into()),
+    }
+}
+
 pub mod execute {
+    use sha2::{Digest, Sha256};
+
     use crate::helper::*;
 
     use super::*;
@@ -176,7 +285,13 @@ pub mod execute {
             terraport_pairs,
         };
         DEX_CONFIGS.save(deps.storage, &config)?;
-        Ok(Response::new())
+
+        let event = Event::new("cremation_set_dex_configs")
+            .add_attribute("terraswap_router", &config.terraswap_router)
+            .add_attribute("terraswap_pairs", join_addresses(&config.terraswap_pairs))
+            .add_attribute("terraport_router", &config.terraport_router)
+            .add_attribute("terraport_pairs", join_addresses(&config.terraport_pairs));
+        Ok(Response::new().add_event(event))
     }
 
     pub fn add_new_pairs(
@@ -197,27 +312,26 @@ pub mod execute {
             .collect::<StdResult<Vec<Addr>>>()?;
 
         let mut dex_configs = DEX_CONFIGS.load(deps.storage).unwrap();
-        match dex {
-            Dex::Terraswap => {
-                for pair in &pairs_addresses {
-                    if dex_configs.terraswap_pairs.contains(pair) {
-                        return Err(StdError::generic_err("Pair already exists").into());
-                    }
-                }
-                dex_configs.terraswap_pairs.extend(pairs_addresses);
-            }
-            Dex::Terraport => {
-                for pair in &pairs_addresses {
-                    if dex_configs.terraport_pairs.contains(pair) {
-                        return Err(StdError::generic_err("Pair already exists").into());
-                    }
-                }
-                dex_configs.terraport_pairs.extend(pairs_addresses);
+        let pairs = match dex {
+            Dex::Terraswap => &mut dex_configs.terraswap_pairs,
+            Dex::Terraport => &mut dex_configs.terraport_pairs,
+        };
+        for pair in &pairs_addresses {
+            if pairs.contains(pair) {
+                return Err(StdError::generic_err("Pair already exists").into());
             }
         }
+        let old_pairs = join_addresses(pairs);
+        pairs.extend(pairs_addresses.clone());
+        let new_pairs = join_addresses(pairs);
         DEX_CONFIGS.save(deps.storage, &dex_configs)?;
 
-        Ok(Response::new())
+        let event = Event::new("cremation_add_new_pairs")
+            .add_attribute("dex", dex.to_string())
+            .add_attribute("added_pairs", join_addresses(&pairs_addresses))
+            .add_attribute("old_pairs", old_pairs)
+            .add_attribute("new_pairs", new_pairs);
+        Ok(Response::new().add_event(event))
     }
 
     pub fn remove_pair(
@@ -234,23 +348,25 @@ pub mod execute {
 
         let pair_address = deps.api.addr_validate(&pair_address)?;
         let mut dex_configs = DEX_CONFIGS.load(deps.storage).unwrap();
-        match dex {
-            Dex::Terraswap => {
-                if !dex_configs.terraswap_pairs.contains(&pair_address) {
-                    return Err(StdError::generic_err("Pair does not exist").into());
-                }
-                dex_configs.terraswap_pairs.retain(|x| x != &pair_address);
-            }
-            Dex::Terraport => {
-                if !dex_configs.terraport_pairs.contains(&pair_address) {
-                    return Err(StdError::generic_err("Pair does not exist").into());
-                }
-                dex_configs.terraport_pairs.retain(|x| x != &pair_address);
-            }
+        let pairs = match dex {
+            Dex::Terraswap => &mut dex_configs.terraswap_pairs,
+            Dex::Terraport => &mut dex_configs.terraport_pairs,
+        };
+        if !pairs.contains(&pair_address) {
+            return Err(StdError::generic_err("Pair does not exist").into());
         }
+        let old_pairs = join_addresses(pairs);
+        pairs.retain(|x| x != &pair_address);
+        let new_pairs = join_addresses(pairs);
         DEX_CONFIGS.save(deps.storage, &dex_configs)?;
-
-        Ok(Response::new())
+        PAIR_TAX_INFO.remove(deps.storage, pair_address.clone());
+
+        let event = Event::new("cremation_remove_pair")
+            .add_attribute("dex", dex.to_string())
+            .add_attribute("removed_pair", pair_address)
+            .add_attribute("old_pairs", old_pairs)
+            .add_attribute("new_pairs", new_pairs);
+        Ok(Response::new().add_event(event))
     }
 
     pub fn update_owner(
@@ -265,7 +381,11 @@ pub mod execute {
         }
         let new_owner = deps.api.addr_validate(&new_owner)?;
         OWNER.save(deps.storage, &new_owner)?;
-        Ok(Response::new())
+
+        let event = Event::new("cremation_update_owner")
+            .add_attribute("old_owner", current_owner)
+            .add_attribute("new_owner", new_owner);
+        Ok(Response::new().add_event(event))
     }
 
     pub fn update_collecting_tax_address(
@@ -288,9 +408,80 @@ pub mod execute {
         COLLECT_TAX_ADDRESS.save(deps.storage, &new_collect_tax_addr)?;
 
         // TAX_FREE_ADDRESSES.save(deps.storage, old_collect_tax_address, &false)?;
-        TAX_FREE_ADDRESSES.save(deps.storage, new_collect_tax_addr, &true)?;
+        TAX_FREE_ADDRESSES.save(deps.storage, new_collect_tax_addr.clone(), &true)?;
 
-        Ok(Response::new())
+        let event = Event::new("cremation_update_collect_tax_address")
+            .add_attribute("old_collect_tax_address", old_collect_tax_address)
+            .add_attribute("new_collect_tax_address", new_collect_tax_addr);
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn set_burning_contract(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        burning_contract: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let old_burning_contract = BURNING_CONTRACT.may_load(deps.storage)?;
+        let new_burning_contract = match burning_contract {
+            Some(burning_contract) => {
+                let burning_contract = deps.api.addr_validate(&burning_contract)?;
+                BURNING_CONTRACT.save(deps.storage, &burning_contract)?;
+                Some(burning_contract)
+            }
+            None => {
+                BURNING_CONTRACT.remove(deps.storage);
+                None
+            }
+        };
+
+        let event = Event::new("cremation_set_burning_contract")
+            .add_attribute(
+                "old_burning_contract",
+                old_burning_contract.map_or("none".to_string(), |addr| addr.to_string()),
+            )
+            .add_attribute(
+                "new_burning_contract",
+                new_burning_contract.map_or("none".to_string(), |addr| addr.to_string()),
+            );
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn sweep(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        denom: String,
+        recipient: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        // keep the send tax of the chain in this contract
+        let balance = deps.querier.query_balance(&env.contract.address, &denom)?;
+        let amount = balance.amount - balance.amount * LUNC_TAX;
+        if amount.is_zero() {
+            return Err(StdError::generic_err("Nothing to sweep").into());
+        }
+        let sweep_coin = Coin { denom, amount };
+
+        let event = Event::new("cremation_sweep")
+            .add_attribute("recipient", &recipient)
+            .add_attribute("amount", sweep_coin.to_string());
+        Ok(Response::new()
+            .add_message(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![sweep_coin],
+            })
+            .add_event(event))
     }
 
     pub fn update_tax_info(
@@ -310,13 +501,17 @@ pub mod execute {
         validate_tax_format(&sell_tax)?;
         validate_tax_format(&transfer_tax)?;
 
+        let old_tax_info = TAX_INFO.load(deps.storage)?;
         let tax_info = TaxInfo {
             buy_tax,
             sell_tax,
             transfer_tax,
         };
         TAX_INFO.save(deps.storage, &tax_info)?;
-        Ok(Response::new())
+
+        let event = Event::new("cremation_update_tax_info")
+            .add_attributes(tax_info_change_attributes(&old_tax_info, &tax_info));
+        Ok(Response::new().add_event(event))
     }
 
     pub fn set_tax_free_address(
@@ -331,8 +526,415 @@ pub mod execute {
             return Err(ContractError::Unauthorized {});
         }
         let address = deps.api.addr_validate(&address)?;
-        TAX_FREE_ADDRESSES.save(deps.storage, address, &tax_free)?;
-        Ok(Response::new())
+        let old_tax_free = TAX_FREE_ADDRESSES
+            .may_load(deps.storage, address.clone())?
+            .unwrap_or(false);
+        TAX_FREE_ADDRESSES.save(deps.storage, address.clone(), &tax_free)?;
+
+        let event = Event::new("cremation_set_tax_free_address")
+            .add_attribute("address", address)
+            .add_attribute("old_tax_free", old_tax_free.to_string())
+            .add_attribute("new_tax_free", tax_free.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn register_ecosystem_contract(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+        role: EcosystemRole,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        let address = deps.api.addr_validate(&address)?;
+        let old_role = ECOSYSTEM_CONTRACTS.may_load(deps.storage, address.clone())?;
+        ECOSYSTEM_CONTRACTS.save(deps.storage, address.clone(), &role)?;
+
+        let event = Event::new("cremation_register_ecosystem_contract")
+            .add_attribute("address", address)
+            .add_attribute(
+                "old_role",
+                old_role.map_or("none".to_string(), |role| role.to_string()),
+            )
+            .add_attribute("new_role", role.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn deregister_ecosystem_contract(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        let address = deps.api.addr_validate(&address)?;
+        let role = ECOSYSTEM_CONTRACTS
+            .may_load(deps.storage, address.clone())?
+            .ok_or_else(|| StdError::generic_err("Ecosystem contract does not exist"))?;
+        ECOSYSTEM_CONTRACTS.remove(deps.storage, address.clone());
+
+        let event = Event::new("cremation_deregister_ecosystem_contract")
+            .add_attribute("address", address)
+            .add_attribute("role", role.to_string());
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn set_pair_tax_info(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        pair_address: String,
+        tax_info: Option<TaxInfo>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let pair_address = deps.api.addr_validate(&pair_address)?;
+        let dex_configs = DEX_CONFIGS.load(deps.storage)?;
+        if !dex_configs.terraswap_pairs.contains(&pair_address)
+            && !dex_configs.terraport_pairs.contains(&pair_address)
+        {
+            return Err(StdError::generic_err("Pair does not exist").into());
+        }
+
+        let old_tax_info = applied_tax_info(deps.storage, &pair_address, &pair_address);
+        match &tax_info {
+            Some(tax_info) => {
+                validate_tax_format(&tax_info.buy_tax)?;
+                validate_tax_format(&tax_info.sell_tax)?;
+                validate_tax_format(&tax_info.transfer_tax)?;
+                PAIR_TAX_INFO.save(deps.storage, pair_address.clone(), tax_info)?;
+            }
+            None => PAIR_TAX_INFO.remove(deps.storage, pair_address.clone()),
+        }
+        let tax_info = applied_tax_info(deps.storage, &pair_address, &pair_address);
+
+        let event = Event::new("cremation_set_pair_tax_info")
+            .add_attribute("pair_address", pair_address)
+            .add_attributes(tax_info_change_attributes(&old_tax_info, &tax_info));
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn set_sell_limit(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        sell_limit: Option<SellLimit>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let old_sell_limit = SELL_LIMIT.may_load(deps.storage)?;
+        let mut event = Event::new("cremation_set_sell_limit");
+        if let Some(old_sell_limit) = old_sell_limit {
+            event = event
+                .add_attribute("old_total", format_limit(old_sell_limit.total))
+                .add_attribute("old_per_address", format_limit(old_sell_limit.per_address))
+                .add_attribute("old_duration", old_sell_limit.duration.to_string());
+        }
+
+        match sell_limit {
+            Some(sell_limit) => {
+                if sell_limit.duration == 0 {
+                    return Err(StdError::generic_err("Duration must be greater than 0").into());
+                }
+                event = event
+                    .add_attribute("new_total", format_limit(sell_limit.total))
+                    .add_attribute("new_per_address", format_limit(sell_limit.per_address))
+                    .add_attribute("new_duration", sell_limit.duration.to_string());
+                SELL_LIMIT.save(deps.storage, &sell_limit)?;
+            }
+            None => SELL_LIMIT.remove(deps.storage),
+        }
+
+        Ok(Response::new().add_event(event))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn permit(
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        owner: String,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
+    ) -> Result<Response, ContractError> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let spender_addr = deps.api.addr_validate(&spender)?;
+        if spender_addr == owner_addr {
+            return Err(ContractError::CannotSetOwnAccount {});
+        }
+        if let Some(expires) = expires {
+            if expires.is_expired(&env.block) {
+                return Err(ContractError::InvalidExpiration {});
+            }
+        }
+
+        let expected_nonce = PERMIT_NONCES
+            .may_load(deps.storage, owner_addr.clone())?
+            .unwrap_or_default();
+        if nonce != expected_nonce {
+            return Err(StdError::generic_err("Invalid permit nonce").into());
+        }
+
+        if !pubkey_matches_address(&pubkey, owner_addr.as_str()) {
+            return Err(StdError::generic_err("Pubkey does not match owner").into());
+        }
+        let payload = PermitPayload {
+            chain_id: env.block.chain_id,
+            contract: env.contract.address.to_string(),
+            owner,
+            spender,
+            amount,
+            expires,
+            nonce,
+        };
+        let hash = Sha256::digest(to_json_vec(&payload)?);
+        let verified = deps
+            .api
+            .secp256k1_verify(&hash, &signature, &pubkey)
+            .map_err(|err| StdError::generic_err(err.to_string()))?;
+        if !verified {
+            return Err(StdError::generic_err("Invalid permit signature").into());
+        }
+
+        PERMIT_NONCES.save(deps.storage, owner_addr.clone(), &(nonce + 1))?;
+        let allowance = AllowanceResponse {
+            allowance: amount,
+            expires: expires.unwrap_or_default(),
+        };
+        ALLOWANCES.save(deps.storage, (&owner_addr, &spender_addr), &allowance)?;
+        ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &owner_addr), &allowance)?;
+
+        let res = Response::new().add_attributes(vec![
+            attr("action", "permit"),
+            attr("owner", owner_addr),
+            attr("spender", spender_addr),
+            attr("amount", amount),
+            attr("nonce", nonce.to_string()),
+        ]);
+        Ok(res)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_vesting(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+        amount: Uint128,
+        start_time: Option<u64>,
+        cliff: u64,
+        duration: u64,
+        revocable: bool,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if amount.is_zero() {
+            return Err(StdError::generic_err("Invalid zero amount").into());
+        }
+        if duration == 0 || cliff > duration {
+            return Err(StdError::generic_err("Invalid vesting duration").into());
+        }
+
+        let address = deps.api.addr_validate(&address)?;
+        let now = env.block.time.seconds();
+        if let Some(schedule) = VESTING_SCHEDULES.may_load(deps.storage, address.clone())? {
+            if !schedule.locked(now).is_zero() {
+                return Err(StdError::generic_err("Vesting schedule already exists").into());
+            }
+        }
+
+        let schedule = VestingSchedule {
+            amount,
+            start_time: start_time.unwrap_or(now),
+            cliff,
+            duration,
+            revocable,
+        };
+        let snapshot = snapshot_balances(deps.storage, &[&owner, &address])?;
+        update_balance_with_tax(deps.storage, &env.block, &owner, &address, amount, None)?;
+        VESTING_SCHEDULES.save(deps.storage, address.clone(), &schedule)?;
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+
+        let event = Event::new("cremation_create_vesting").add_attributes(vec![
+            attr("address", address),
+            attr("amount", amount),
+            attr("start_time", schedule.start_time.to_string()),
+            attr("cliff", cliff.to_string()),
+            attr("duration", duration.to_string()),
+            attr("revocable", revocable.to_string()),
+        ]);
+        Ok(Response::new().add_submessages(hook_msgs).add_event(event))
+    }
+
+    pub fn revoke_vesting(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        address: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let address = deps.api.addr_validate(&address)?;
+        let schedule = VESTING_SCHEDULES
+            .may_load(deps.storage, address.clone())?
+            .ok_or_else(|| StdError::generic_err("Vesting schedule does not exist"))?;
+        if !schedule.revocable {
+            return Err(StdError::generic_err("Vesting schedule is not revocable").into());
+        }
+
+        // vested tokens stay with the address, so the schedule is dropped before moving the rest
+        let revoked = schedule.locked(env.block.time.seconds());
+        VESTING_SCHEDULES.remove(deps.storage, address.clone());
+        let snapshot = snapshot_balances(deps.storage, &[&address, &owner])?;
+        update_balance_with_tax(deps.storage, &env.block, &address, &owner, revoked, None)?;
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+
+        let event = Event::new("cremation_revoke_vesting").add_attributes(vec![
+            attr("address", address),
+            attr("revoked_amount", revoked),
+        ]);
+        Ok(Response::new().add_submessages(hook_msgs).add_event(event))
+    }
+
+    pub fn add_balance_hook(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract: String,
+        addresses: Option<Vec<String>>,
+        gas_limit: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let contract = deps.api.addr_validate(&contract)?;
+        if BALANCE_HOOKS.has(deps.storage, contract.clone()) {
+            return Err(StdError::generic_err("Hook already exists").into());
+        }
+        let addresses = addresses
+            .map(|addresses| {
+                addresses
+                    .into_iter()
+                    .map(|addr| deps.api.addr_validate(&addr))
+                    .collect::<StdResult<Vec<Addr>>>()
+            })
+            .transpose()?;
+        let gas_limit = gas_limit.unwrap_or(DEFAULT_BALANCE_HOOK_GAS_LIMIT);
+        if gas_limit == 0 {
+            return Err(StdError::generic_err("Hook gas limit must be positive").into());
+        }
+
+        let event = Event::new("cremation_add_balance_hook")
+            .add_attribute("contract", &contract)
+            .add_attribute(
+                "addresses",
+                match &addresses {
+                    Some(addresses) => join_addresses(addresses),
+                    None => "*".to_string(),
+                },
+            )
+            .add_attribute("gas_limit", gas_limit.to_string());
+        BALANCE_HOOKS.save(
+            deps.storage,
+            contract,
+            &BalanceHook {
+                addresses,
+                gas_limit,
+            },
+        )?;
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn remove_balance_hook(
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        contract: String,
+    ) -> Result<Response, ContractError> {
+        let owner = OWNER.load(deps.storage)?;
+        if info.sender != owner {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let contract = deps.api.addr_validate(&contract)?;
+        if !BALANCE_HOOKS.has(deps.storage, contract.clone()) {
+            return Err(StdError::generic_err("Hook does not exist").into());
+        }
+        BALANCE_HOOKS.remove(deps.storage, contract.clone());
+
+        let event = Event::new("cremation_remove_balance_hook").add_attribute("contract", contract);
+        Ok(Response::new().add_event(event))
+    }
+
+    pub fn mint(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        let rcpt_addr = deps.api.addr_validate(&recipient)?;
+        let snapshot = snapshot_balances(deps.storage, &[&rcpt_addr])?;
+
+        let res = execute_mint(deps.branch(), env, info, recipient, amount)?;
+
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        Ok(res.add_submessages(hook_msgs))
+    }
+
+    pub fn burn(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        assert_vested_balance(deps.storage, &env.block, &info.sender, amount)?;
+        let snapshot = snapshot_balances(deps.storage, &[&info.sender])?;
+
+        let res = execute_burn(deps.branch(), env, info, amount)?;
+
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        Ok(res.add_submessages(hook_msgs))
+    }
+
+    pub fn burn_from(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        owner: String,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        assert_vested_balance(deps.storage, &env.block, &owner_addr, amount)?;
+        let snapshot = snapshot_balances(deps.storage, &[&owner_addr])?;
+
+        let res = execute_burn_from(deps.branch(), env, info, owner, amount)?;
+
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        Ok(res.add_submessages(hook_msgs))
     }
 
     pub fn send(
@@ -347,8 +949,32 @@ pub mod execute {
         let rcpt_addr = deps.api.addr_validate(&contract)?;
         let is_transfer = false;
         let tax_amount = compute_tax(deps.storage, &sender_addr, &rcpt_addr, amount, is_transfer);
+        let tax_category = tax_category(deps.storage, &sender_addr, &rcpt_addr, is_transfer);
 
-        update_balance_with_tax(deps.storage, &sender_addr, &rcpt_addr, amount, tax_amount)?;
+        record_sell(deps.storage, &env, &sender_addr, &rcpt_addr, amount)?;
+        let collect_tax_addr = COLLECT_TAX_ADDRESS.load(deps.storage)?;
+        let snapshot =
+            snapshot_balances(deps.storage, &[&sender_addr, &rcpt_addr, &collect_tax_addr])?;
+
+        update_balance_with_tax(
+            deps.storage,
+            &env.block,
+            &sender_addr,
+            &rcpt_addr,
+            amount,
+            tax_amount,
+        )?;
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        let tax_collector = tax_amount.map(|_| &collect_tax_addr);
+        let event = transfer_event(
+            "send",
+            &sender_addr,
+            &rcpt_addr,
+            amount,
+            tax_category,
+            tax_amount,
+            tax_collector,
+        );
 
         let mut attrs = vec![
             attr("action", "send"),
@@ -361,20 +987,24 @@ pub mod execute {
         }
 
         // create a send message
-        let mut messages = vec![Cw20ReceiveMsg {
+        let messages = vec![Cw20ReceiveMsg {
             sender: sender_addr.to_string(),
             amount,
             msg,
         }
         .into_cosmos_msg(contract)?];
 
-        let msg_opt = swap_collected_tax_to_native(deps, env, &sender_addr, &rcpt_addr)?;
-        if let Some(swap_msg) = msg_opt {
+        let swap_msg_opt = swap_collected_tax_to_native(deps, env, &sender_addr, &rcpt_addr)?;
+        if swap_msg_opt.is_some() {
             attrs.push(attr("action", "collected_tax_swap"));
-            messages.push(swap_msg);
         }
 
-        let res = Response::new().add_messages(messages).add_attributes(attrs);
+        let res = Response::new()
+            .add_messages(messages)
+            .add_submessages(swap_msg_opt)
+            .add_submessages(hook_msgs)
+            .add_attributes(attrs)
+            .add_event(event);
         Ok(res)
     }
 
@@ -391,10 +1021,35 @@ pub mod execute {
         let rcpt_addr = deps.api.addr_validate(&contract)?;
         let is_transfer = false;
         let tax_amount = compute_tax(deps.storage, &owner_addr, &rcpt_addr, amount, is_transfer);
+        let tax_category = tax_category(deps.storage, &owner_addr, &rcpt_addr, is_transfer);
 
         // deduct allowance before doing anything else have enough allowance
         deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
-        update_balance_with_tax(deps.storage, &owner_addr, &rcpt_addr, amount, tax_amount)?;
+        record_sell(deps.storage, &env, &owner_addr, &rcpt_addr, amount)?;
+        let collect_tax_addr = COLLECT_TAX_ADDRESS.load(deps.storage)?;
+        let snapshot =
+            snapshot_balances(deps.storage, &[&owner_addr, &rcpt_addr, &collect_tax_addr])?;
+
+        update_balance_with_tax(
+            deps.storage,
+            &env.block,
+            &owner_addr,
+            &rcpt_addr,
+            amount,
+            tax_amount,
+        )?;
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        let tax_collector = tax_amount.map(|_| &collect_tax_addr);
+        let event = transfer_event(
+            "send_from",
+            &owner_addr,
+            &rcpt_addr,
+            amount,
+            tax_category,
+            tax_amount,
+            tax_collector,
+        )
+        .add_attribute("by", &info.sender);
 
         let mut attrs = vec![
             attr("action", "send_from"),
@@ -408,26 +1063,30 @@ pub mod execute {
         }
 
         // create a send message
-        let mut messages = vec![Cw20ReceiveMsg {
+        let messages = vec![Cw20ReceiveMsg {
             sender: info.sender.into(),
             amount,
             msg,
         }
         .into_cosmos_msg(contract)?];
 
-        let msg_opt = swap_collected_tax_to_native(deps, env, &owner_addr, &rcpt_addr)?;
-        if let Some(swap_msg) = msg_opt {
+        let swap_msg_opt = swap_collected_tax_to_native(deps, env, &owner_addr, &rcpt_addr)?;
+        if swap_msg_opt.is_some() {
             attrs.push(attr("action", "collected_tax_swap"));
-            messages.push(swap_msg)
         }
 
-        let res = Response::new().add_messages(messages).add_attributes(attrs);
+        let res = Response::new()
+            .add_messages(messages)
+            .add_submessages(swap_msg_opt)
+            .add_submessages(hook_msgs)
+            .add_attributes(attrs)
+            .add_event(event);
         Ok(res)
     }
 
     pub fn transfer(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         recipient: String,
         amount: Uint128,
@@ -436,8 +1095,32 @@ pub mod execute {
         let rcpt_addr = deps.api.addr_validate(&recipient)?;
         let is_transfer = true;
         let tax_amount = compute_tax(deps.storage, &sender_addr, &rcpt_addr, amount, is_transfer);
+        let tax_category = tax_category(deps.storage, &sender_addr, &rcpt_addr, is_transfer);
+
+        record_sell(deps.storage, &env, &sender_addr, &rcpt_addr, amount)?;
+        let collect_tax_addr = COLLECT_TAX_ADDRESS.load(deps.storage)?;
+        let snapshot =
+            snapshot_balances(deps.storage, &[&sender_addr, &rcpt_addr, &collect_tax_addr])?;
 
-        update_balance_with_tax(deps.storage, &sender_addr, &rcpt_addr, amount, tax_amount)?;
+        update_balance_with_tax(
+            deps.storage,
+            &env.block,
+            &sender_addr,
+            &rcpt_addr,
+            amount,
+            tax_amount,
+        )?;
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        let tax_collector = tax_amount.map(|_| &collect_tax_addr);
+        let event = transfer_event(
+            "transfer",
+            &sender_addr,
+            &rcpt_addr,
+            amount,
+            tax_category,
+            tax_amount,
+            tax_collector,
+        );
 
         let mut attrs = vec![
             attr("action", "transfer"),
@@ -449,7 +1132,10 @@ pub mod execute {
             attrs.push(attr("cw20_tax_amount", tax));
         }
 
-        Ok(Response::new().add_attributes(attrs))
+        Ok(Response::new()
+            .add_submessages(hook_msgs)
+            .add_attributes(attrs)
+            .add_event(event))
     }
 
     pub fn transfer_from(
@@ -464,10 +1150,35 @@ pub mod execute {
         let owner_addr = deps.api.addr_validate(&owner)?;
         let is_transfer = true;
         let tax_amount = compute_tax(deps.storage, &owner_addr, &rcpt_addr, amount, is_transfer);
+        let tax_category = tax_category(deps.storage, &owner_addr, &rcpt_addr, is_transfer);
 
         // deduct allowance before doing anything else have enough allowance
         deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
-        update_balance_with_tax(deps.storage, &owner_addr, &rcpt_addr, amount, tax_amount)?;
+        record_sell(deps.storage, &env, &owner_addr, &rcpt_addr, amount)?;
+        let collect_tax_addr = COLLECT_TAX_ADDRESS.load(deps.storage)?;
+        let snapshot =
+            snapshot_balances(deps.storage, &[&owner_addr, &rcpt_addr, &collect_tax_addr])?;
+
+        update_balance_with_tax(
+            deps.storage,
+            &env.block,
+            &owner_addr,
+            &rcpt_addr,
+            amount,
+            tax_amount,
+        )?;
+        let hook_msgs = balance_hook_msgs(deps.storage, snapshot)?;
+        let tax_collector = tax_amount.map(|_| &collect_tax_addr);
+        let event = transfer_event(
+            "transfer_from",
+            &owner_addr,
+            &rcpt_addr,
+            amount,
+            tax_category,
+            tax_amount,
+            tax_collector,
+        )
+        .add_attribute("by", &info.sender);
 
         let mut attrs = vec![
             attr("action", "transfer_from"),
@@ -480,16 +1191,21 @@ pub mod execute {
             attrs.push(attr("cw20_tax_amount", tax));
         }
 
-        Ok(Response::new().add_attributes(attrs))
+        Ok(Response::new()
+            .add_submessages(hook_msgs)
+            .add_attributes(attrs)
+            .add_event(event))
     }
 
     pub fn update_balance_with_tax(
         storage: &mut dyn Storage,
+        block: &BlockInfo,
         from: &Addr,
         to: &Addr,
         amount: Uint128,
         tax_amount: Option<Uint128>,
     ) -> StdResult<()> {
+        assert_vested_balance(storage, block, from, amount)?;
         // Update sender balance, return error if insufficient funds
         BALANCES.update(storage, &from, |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
@@ -530,60 +1246,236 @@ pub mod execute {
         amount: Uint128,
         is_transfer: bool,
     ) -> Option<Uint128> {
+        let tax_info = applied_tax_info(store, from, to);
+        let tax = match tax_category(store, from, to, is_transfer) {
+            TaxCategory::Buy => tax_info.buy_tax,
+            TaxCategory::Sell => tax_info.sell_tax,
+            TaxCategory::Transfer => tax_info.transfer_tax,
+            TaxCategory::None => None,
+        };
+
+        tax.map(|tax| {
+            amount
+                .checked_mul(tax.numerator)
+                .unwrap()
+                .checked_div(tax.denominator)
+                .unwrap()
+        })
+    }
+
+    pub fn tax_category(
+        store: &dyn Storage,
+        from: &Addr,
+        to: &Addr,
+        is_transfer: bool,
+    ) -> TaxCategory {
         let dex_configs = DEX_CONFIGS.load(store).unwrap();
-        let tax_info = TAX_INFO.load(store).unwrap();
+        let tax_info = applied_tax_info(store, from, to);
 
-        if TAX_FREE_ADDRESSES.has(store, from.clone()) || TAX_FREE_ADDRESSES.has(store, to.clone())
-        {
-            return None;
+        if is_tax_free(store, from, to) {
+            return TaxCategory::None;
         }
 
-        let is_buy = tax_info.buy_tax.is_some() && is_buy_operation(&dex_configs, &from, &to);
-        let is_sell = tax_info.sell_tax.is_some() && is_sell_operation(&dex_configs, &from, &to);
+        let is_buy = tax_info.buy_tax.is_some() && is_buy_operation(&dex_configs, from, to);
+        let is_sell = tax_info.sell_tax.is_some() && is_sell_operation(&dex_configs, from, to);
         let is_transfer = tax_info.transfer_tax.is_some() && is_transfer;
 
         match (is_transfer, is_buy, is_sell) {
-            (true, false, false) => match tax_info.transfer_tax {
-                Some(tax) => {
-                    let tax_amount = amount
-                        .checked_mul(tax.numerator)
-                        .unwrap()
-                        .checked_div(tax.denominator)
-                        .unwrap();
-                    return Some(tax_amount);
-                }
-                None => {
-                    return None;
-                }
-            },
-            (_, true, false) => match tax_info.buy_tax {
-                Some(tax) => {
-                    let tax_amount = amount
-                        .checked_mul(tax.numerator)
-                        .unwrap()
-                        .checked_div(tax.denominator)
-                        .unwrap();
-                    return Some(tax_amount);
-                }
-                None => {
-                    return None;
-                }
-            },
-            (_, false, true) => match tax_info.sell_tax {
-                Some(tax) => {
-                    let tax_amount = amount
-                        .checked_mul(tax.numerator)
-                        .unwrap()
-                        .checked_div(tax.denominator)
-                        .unwrap();
-                    return Some(tax_amount);
-                }
-                None => {
-                    return None;
+            (true, false, false) => TaxCategory::Transfer,
+            (_, true, false) => TaxCategory::Buy,
+            (_, false, true) => TaxCategory::Sell,
+            _ => TaxCategory::None,
+        }
+    }
+
+    // tax free addresses on either side, or ecosystem contracts exempted in this direction
+    pub fn is_tax_free(store: &dyn Storage, from: &Addr, to: &Addr) -> bool {
+        is_tax_free_sender(store, from) || is_tax_free_recipient(store, to)
+    }
+
+    pub fn is_tax_free_sender(store: &dyn Storage, address: &Addr) -> bool {
+        is_tax_free_address(store, address)
+            || ECOSYSTEM_CONTRACTS
+                .may_load(store, address.clone())
+                .unwrap()
+                .is_some_and(|role| role.exempts_outgoing())
+    }
+
+    pub fn is_tax_free_recipient(store: &dyn Storage, address: &Addr) -> bool {
+        is_tax_free_address(store, address)
+            || ECOSYSTEM_CONTRACTS
+                .may_load(store, address.clone())
+                .unwrap()
+                .is_some_and(|role| role.exempts_incoming())
+    }
+
+    // unset addresses are stored as false, so the flag is read rather than the key
+    fn is_tax_free_address(store: &dyn Storage, address: &Addr) -> bool {
+        TAX_FREE_ADDRESSES
+            .may_load(store, address.clone())
+            .unwrap()
+            .unwrap_or(false)
+    }
+
+    // the pair override of the sender (buy) or the recipient (sell), falling back to the global tax info
+    pub fn applied_tax_info(store: &dyn Storage, from: &Addr, to: &Addr) -> TaxInfo {
+        PAIR_TAX_INFO
+            .may_load(store, from.clone())
+            .unwrap()
+            .or_else(|| PAIR_TAX_INFO.may_load(store, to.clone()).unwrap())
+            .unwrap_or_else(|| TAX_INFO.load(store).unwrap())
+    }
+
+    // enforce the sell limit of a non tax free sell, and count it in the current window
+    fn record_sell(
+        storage: &mut dyn Storage,
+        env: &Env,
+        from: &Addr,
+        to: &Addr,
+        amount: Uint128,
+    ) -> Result<(), ContractError> {
+        let sell_limit = match SELL_LIMIT.may_load(storage)? {
+            Some(sell_limit) => sell_limit,
+            None => return Ok(()),
+        };
+        // the proxy hands the tokens on to the pair tax free, so sells through
+        // it are counted on the seller's send into the proxy
+        let into_proxy = matches!(
+            ECOSYSTEM_CONTRACTS.may_load(storage, to.clone())?,
+            Some(EcosystemRole::Proxy)
+        );
+        if into_proxy {
+            if is_tax_free_sender(storage, from) {
+                return Ok(());
+            }
+        } else {
+            if is_tax_free(storage, from, to) {
+                return Ok(());
+            }
+            let dex_configs = DEX_CONFIGS.load(storage)?;
+            if !is_sell_operation(&dex_configs, from, to) {
+                return Ok(());
+            }
+        }
+
+        let now = env.block.time.seconds();
+        let total_sold = accumulate_sold(
+            TOTAL_SOLD.may_load(storage)?,
+            amount,
+            now,
+            sell_limit.duration,
+        );
+        if let Some(total) = sell_limit.total {
+            if total_sold.amount() > total {
+                return Err(StdError::generic_err("Exceed total sell limit").into());
+            }
+        }
+
+        let addr_sold = accumulate_sold(
+            SOLD_BY_ADDRESS.may_load(storage, from.clone())?,
+            amount,
+            now,
+            sell_limit.duration,
+        );
+        if let Some(per_address) = sell_limit.per_address {
+            if addr_sold.amount() > per_address {
+                return Err(StdError::generic_err("Exceed sell limit per address").into());
+            }
+        }
+
+        TOTAL_SOLD.save(storage, &total_sold)?;
+        SOLD_BY_ADDRESS.save(storage, from.clone(), &addr_sold)?;
+        Ok(())
+    }
+
+    // a debit must leave enough balance to cover the still locked vesting tokens
+    fn assert_vested_balance(
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        addr: &Addr,
+        amount: Uint128,
+    ) -> StdResult<()> {
+        let schedule = match VESTING_SCHEDULES.may_load(storage, addr.clone())? {
+            Some(schedule) => schedule,
+            None => return Ok(()),
+        };
+        let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default();
+        // insufficient funds are reported by the debit itself
+        let remaining = balance.saturating_sub(amount);
+        if balance >= amount && remaining < schedule.locked(block.time.seconds()) {
+            return Err(StdError::generic_err(
+                "Cannot transfer locked vesting tokens",
+            ));
+        }
+        Ok(())
+    }
+
+    fn format_limit(limit: Option<Uint128>) -> String {
+        match limit {
+            Some(limit) => limit.to_string(),
+            None => "none".to_string(),
+        }
+    }
+
+    fn snapshot_balances(
+        storage: &dyn Storage,
+        addresses: &[&Addr],
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        let mut snapshot: Vec<(Addr, Uint128)> = vec![];
+        for addr in addresses {
+            if snapshot
+                .iter()
+                .any(|(snapshot_addr, _)| snapshot_addr == *addr)
+            {
+                continue;
+            }
+            let balance = BALANCES.may_load(storage, addr)?.unwrap_or_default();
+            snapshot.push(((*addr).clone(), balance));
+        }
+        Ok(snapshot)
+    }
+
+    // notify hook contracts watching any address whose balance changed since the snapshot
+    fn balance_hook_msgs(
+        storage: &dyn Storage,
+        snapshot: Vec<(Addr, Uint128)>,
+    ) -> StdResult<Vec<SubMsg>> {
+        let hooks = BALANCE_HOOKS
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut msgs = vec![];
+        for (address, old) in snapshot {
+            let new = BALANCES.may_load(storage, &address)?.unwrap_or_default();
+            if old == new {
+                continue;
+            }
+
+            for (contract, hook) in hooks.iter() {
+                let watched = match &hook.addresses {
+                    Some(addresses) => addresses.contains(&address),
+                    None => true,
+                };
+                if !watched {
+                    continue;
                 }
-            },
-            _ => None,
+
+                let msg = WasmMsg::Execute {
+                    contract_addr: contract.to_string(),
+                    msg: to_json_binary(&BalanceHookMsg::BalanceChangedHook {
+                        address: address.to_string(),
+                        old,
+                        new,
+                    })?,
+                    funds: vec![],
+                };
+                msgs.push(
+                    SubMsg::reply_on_error(msg, BALANCE_HOOK_REPLY_ID)
+                        .with_gas_limit(hook.gas_limit),
+                );
+            }
         }
+        Ok(msgs)
     }
 
     fn swap_collected_tax_to_native(
@@ -591,7 +1483,7 @@ pub mod execute {
         env: Env,
         from: &Addr,
         to: &Addr,
-    ) -> Result<Option<CosmosMsg>, ContractError> {
+    ) -> Result<Option<SubMsg>, ContractError> {
         let dex_configs = DEX_CONFIGS.load(deps.storage)?;
 
         // Only collect tax with sell operation
@@ -652,6 +1544,13 @@ pub mod execute {
             update_fn,
         )?;
 
+        // swap collected tax to this contract to burn it in reply, or directly to the collector
+        let burning_contract = BURNING_CONTRACT.may_load(deps.storage)?;
+        let swap_recipient = match burning_contract {
+            Some(_) => env.contract.address.clone(),
+            None => collect_tax_addr.clone(),
+        };
+
         // swap collected tax to native token
         let cw20_send_msg = ExecuteMsg::SendFrom {
             owner: collect_tax_addr.to_string(),
@@ -659,7 +1558,7 @@ pub mod execute {
             amount: collected_tax_amount,
             msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
                 operations: vec![swap_operation],
-                to: Some(collect_tax_addr.to_string()),
+                to: Some(swap_recipient.to_string()),
                 minimum_receive: None,
                 deadline: None,
             })
@@ -670,11 +1569,23 @@ pub mod execute {
             msg: to_json_binary(&cw20_send_msg).unwrap(),
             funds: vec![],
         };
-        Ok(Some(msg.into()))
+        if burning_contract.is_none() {
+            return Ok(Some(SubMsg::new(msg)));
+        }
+
+        let pre_swap_balance = deps
+            .querier
+            .query_balance(&env.contract.address, "uluna")?
+            .amount;
+        PRE_TAX_SWAP_BALANCE.save(deps.storage, &pre_swap_balance)?;
+        Ok(Some(SubMsg::reply_on_success(
+            msg,
+            BURN_COLLECTED_TAX_REPLY_ID,
+        )))
     }
 }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         // ======= Existed queries from cw20-base =======
         QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
@@ -708,14 +1619,35 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::DexConfigs {} => to_json_binary(&query::dex_configs(deps)?),
         QueryMsg::Owner {} => to_json_binary(&query::owner(deps)?),
         QueryMsg::CollectTaxAddress {} => to_json_binary(&query::collect_tax_address(deps)?),
+        QueryMsg::BurningContract {} => to_json_binary(&query::burning_contract(deps)?),
         QueryMsg::TaxInfo {} => to_json_binary(&query::tax_info(deps)?),
         QueryMsg::TaxFreeAddress { address } => {
             to_json_binary(&query::tax_free_address(deps, address)?)
         }
+        QueryMsg::SellLimit {} => to_json_binary(&query::sell_limit(deps)?),
+        QueryMsg::SellAllowance { address } => {
+            to_json_binary(&query::sell_allowance(deps, env, address)?)
+        }
+        QueryMsg::VestingInfo { address } => {
+            to_json_binary(&query::vesting_info(deps, env, address)?)
+        }
+        QueryMsg::PermitNonce { owner } => to_json_binary(&query::permit_nonce(deps, owner)?),
+        QueryMsg::BalanceHooks {} => to_json_binary(&query::balance_hooks(deps)?),
+        QueryMsg::EcosystemContracts { role } => {
+            to_json_binary(&query::ecosystem_contracts(deps, role)?)
+        }
+        QueryMsg::PairTaxInfos {} => to_json_binary(&query::pair_tax_infos(deps)?),
+        QueryMsg::AppliedTaxInfo { pair_address } => {
+            to_json_binary(&query::applied_tax_info(deps, pair_address)?)
+        }
     }
 }
 
 pub mod query {
+    use crate::helper::{accumulate_sold, tax_rate};
+
+    use super::execute::{is_tax_free_recipient, is_tax_free_sender};
+
     use super::*;
 
     pub fn dex_configs(deps: Deps) -> StdResult<DexConfigsResponse> {
@@ -740,33 +1672,167 @@ pub mod query {
         })
     }
 
+    pub fn burning_contract(deps: Deps) -> StdResult<BurningContractResponse> {
+        let burning_contract = BURNING_CONTRACT.may_load(deps.storage)?;
+        Ok(BurningContractResponse { burning_contract })
+    }
+
     pub fn tax_info(deps: Deps) -> StdResult<TaxInfoResponse> {
         let tax_info = TAX_INFO.load(deps.storage)?;
-        let buy_tax = match tax_info.buy_tax {
-            Some(tax) => Decimal::from_ratio(tax.numerator, tax.denominator),
-            None => Decimal::zero(),
-        };
-        let sell_tax = match tax_info.sell_tax {
-            Some(tax) => Decimal::from_ratio(tax.numerator, tax.denominator),
-            None => Decimal::zero(),
-        };
-        let transfer_tax = match tax_info.transfer_tax {
-            Some(tax) => Decimal::from_ratio(tax.numerator, tax.denominator),
-            None => Decimal::zero(),
-        };
-        Ok(TaxInfoResponse {
-            buy_tax,
-            sell_tax,
-            transfer_tax,
-        })
+        Ok(tax_info_response(&tax_info))
+    }
+
+    pub fn ecosystem_contracts(
+        deps: Deps,
+        role: Option<EcosystemRole>,
+    ) -> StdResult<EcosystemContractsResponse> {
+        let contracts = ECOSYSTEM_CONTRACTS
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| match (item, &role) {
+                (Ok((_, contract_role)), Some(role)) => contract_role == role,
+                _ => true,
+            })
+            .map(|item| item.map(|(address, role)| EcosystemContract { address, role }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(EcosystemContractsResponse { contracts })
+    }
+
+    pub fn pair_tax_infos(deps: Deps) -> StdResult<PairTaxInfosResponse> {
+        let pairs = PAIR_TAX_INFO
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(pair_address, tax_info)| PairTaxInfoResponse {
+                    pair_address,
+                    tax_info: tax_info_response(&tax_info),
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(PairTaxInfosResponse { pairs })
+    }
+
+    pub fn applied_tax_info(deps: Deps, pair_address: String) -> StdResult<TaxInfoResponse> {
+        let pair_address = deps.api.addr_validate(&pair_address)?;
+        let tax_info = execute::applied_tax_info(deps.storage, &pair_address, &pair_address);
+        Ok(tax_info_response(&tax_info))
+    }
+
+    fn tax_info_response(tax_info: &TaxInfo) -> TaxInfoResponse {
+        TaxInfoResponse {
+            buy_tax: tax_rate(&tax_info.buy_tax),
+            sell_tax: tax_rate(&tax_info.sell_tax),
+            transfer_tax: tax_rate(&tax_info.transfer_tax),
+        }
     }
 
     pub fn tax_free_address(deps: Deps, address: String) -> StdResult<TaxFreeAddressResponse> {
         let addr = deps.api.addr_validate(&address)?;
-        let tax_free_opt = TAX_FREE_ADDRESSES.may_load(deps.storage, addr.clone())?;
-        match tax_free_opt {
-            Some(tax_free) => Ok(TaxFreeAddressResponse { tax_free }),
-            None => Ok(TaxFreeAddressResponse { tax_free: false }),
+        let incoming = is_tax_free_recipient(deps.storage, &addr);
+        let outgoing = is_tax_free_sender(deps.storage, &addr);
+        Ok(TaxFreeAddressResponse {
+            tax_free: incoming && outgoing,
+            incoming,
+            outgoing,
+        })
+    }
+
+    pub fn sell_limit(deps: Deps) -> StdResult<SellLimitResponse> {
+        let sell_limit = SELL_LIMIT.may_load(deps.storage)?;
+        Ok(SellLimitResponse { sell_limit })
+    }
+
+    pub fn sell_allowance(
+        deps: Deps,
+        env: Env,
+        address: String,
+    ) -> StdResult<SellAllowanceResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let sell_limit = match SELL_LIMIT.may_load(deps.storage)? {
+            Some(sell_limit) if !is_tax_free_sender(deps.storage, &address) => sell_limit,
+            _ => {
+                return Ok(SellAllowanceResponse {
+                    remaining: None,
+                    reset_time: None,
+                })
+            }
+        };
+
+        let now = env.block.time.seconds();
+        let limits = [
+            (sell_limit.total, TOTAL_SOLD.may_load(deps.storage)?),
+            (
+                sell_limit.per_address,
+                SOLD_BY_ADDRESS.may_load(deps.storage, address)?,
+            ),
+        ];
+
+        // the tightest of the total and per address limits, `reset_time` being when its oldest
+        // counted sell leaves the rolling window
+        let mut remaining: Option<Uint128> = None;
+        let mut reset_time: Option<u64> = None;
+        for (limit, sold) in limits {
+            let limit = match limit {
+                Some(limit) => limit,
+                None => continue,
+            };
+            let sold = accumulate_sold(sold, Uint128::zero(), now, sell_limit.duration);
+            let limit_remaining = limit.saturating_sub(sold.amount());
+            if remaining.is_none_or(|remaining| limit_remaining < remaining) {
+                remaining = Some(limit_remaining);
+                reset_time = sold.next_release(sell_limit.duration);
+            }
         }
+
+        Ok(SellAllowanceResponse {
+            remaining,
+            reset_time,
+        })
+    }
+
+    pub fn vesting_info(deps: Deps, env: Env, address: String) -> StdResult<VestingInfoResponse> {
+        let address = deps.api.addr_validate(&address)?;
+        let now = env.block.time.seconds();
+        let schedule = VESTING_SCHEDULES.may_load(deps.storage, address)?;
+        let (vested, locked, next_unlock) = match &schedule {
+            Some(schedule) => {
+                let cliff_end = schedule.start_time + schedule.cliff;
+                let next_unlock = if now < cliff_end {
+                    Some(cliff_end)
+                } else if now < schedule.end_time() {
+                    Some(now + 1)
+                } else {
+                    None
+                };
+                (schedule.vested(now), schedule.locked(now), next_unlock)
+            }
+            None => (Uint128::zero(), Uint128::zero(), None),
+        };
+        Ok(VestingInfoResponse {
+            schedule,
+            vested,
+            locked,
+            next_unlock,
+        })
+    }
+
+    pub fn permit_nonce(deps: Deps, owner: String) -> StdResult<PermitNonceResponse> {
+        let owner = deps.api.addr_validate(&owner)?;
+        let nonce = PERMIT_NONCES
+            .may_load(deps.storage, owner)?
+            .unwrap_or_default();
+        Ok(PermitNonceResponse { nonce })
+    }
+
+    pub fn balance_hooks(deps: Deps) -> StdResult<BalanceHooksResponse> {
+        let hooks = BALANCE_HOOKS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(contract, hook)| BalanceHookInfo {
+                    contract,
+                    addresses: hook.addresses,
+                    gas_limit: hook.gas_limit,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(BalanceHooksResponse { hooks })
     }
 }
@@ -1,8 +1,32 @@
-use cosmwasm_std::{Addr, StdError, Uint128};
+use std::fmt;
+
+use bech32::ToBase32;
+use cosmwasm_std::{attr, Addr, Attribute, Decimal, Event, StdError, Uint128};
 use cw20_base::ContractError;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 use crate::state::*;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaxCategory {
+    Buy,
+    Sell,
+    Transfer,
+    None,
+}
+
+impl fmt::Display for TaxCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaxCategory::Buy => write!(f, "buy"),
+            TaxCategory::Sell => write!(f, "sell"),
+            TaxCategory::Transfer => write!(f, "transfer"),
+            TaxCategory::None => write!(f, "none"),
+        }
+    }
+}
+
 // receive token from terraswap pair, or terraport pair
 pub fn is_buy_operation(dex_configs: &DexConfigs, from: &Addr, to: &Addr) -> bool {
     let buy_from_terraswap = from != to
@@ -48,3 +72,96 @@ pub fn validate_tax_format(tax: &Option<FractionFormat>) -> Result<(), ContractE
     }
     Ok(())
 }
+
+// number of buckets a sell window is split into, bounding the stored buckets per address
+pub const SELL_WINDOW_BUCKETS: u64 = 24;
+
+// drop the buckets that left the window ending at `now` and add `amount` to the latest bucket,
+// opening a new one once it spans `duration / SELL_WINDOW_BUCKETS`. A bucket leaves the window
+// `duration` after its first sell, so later sells in it are released up to one bucket early
+pub fn accumulate_sold(
+    sold: Option<SoldWindow>,
+    amount: Uint128,
+    now: u64,
+    duration: u64,
+) -> SoldWindow {
+    let bucket_size = (duration / SELL_WINDOW_BUCKETS).max(1);
+    let mut sold = sold.unwrap_or_default();
+    sold.buckets.retain(|bucket| bucket.start + duration > now);
+    if amount.is_zero() {
+        return sold;
+    }
+
+    match sold.buckets.last_mut() {
+        Some(bucket) if bucket.start <= now && now < bucket.start + bucket_size => {
+            bucket.amount += amount;
+        }
+        _ => sold.buckets.push(SoldBucket { start: now, amount }),
+    }
+    sold
+}
+
+// whether `address` is the bech32 account address of a compressed secp256k1 pubkey
+pub fn pubkey_matches_address(pubkey: &[u8], address: &str) -> bool {
+    if pubkey.len() != 33 {
+        return false;
+    }
+    let (hrp, _, variant) = match bech32::decode(address) {
+        Ok(decoded) => decoded,
+        Err(_) => return false,
+    };
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    match bech32::encode(&hrp, hash.to_base32(), variant) {
+        Ok(derived) => derived == address,
+        Err(_) => false,
+    }
+}
+
+pub fn tax_rate(tax: &Option<FractionFormat>) -> Decimal {
+    match tax {
+        Some(tax) => Decimal::from_ratio(tax.numerator, tax.denominator),
+        None => Decimal::zero(),
+    }
+}
+
+pub fn tax_info_change_attributes(old: &TaxInfo, new: &TaxInfo) -> Vec<Attribute> {
+    vec![
+        attr("old_buy_tax", tax_rate(&old.buy_tax).to_string()),
+        attr("new_buy_tax", tax_rate(&new.buy_tax).to_string()),
+        attr("old_sell_tax", tax_rate(&old.sell_tax).to_string()),
+        attr("new_sell_tax", tax_rate(&new.sell_tax).to_string()),
+        attr("old_transfer_tax", tax_rate(&old.transfer_tax).to_string()),
+        attr("new_transfer_tax", tax_rate(&new.transfer_tax).to_string()),
+    ]
+}
+
+pub fn join_addresses(addresses: &[Addr]) -> String {
+    addresses
+        .iter()
+        .map(|addr| addr.as_str())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+// emitted as `wasm-cremation_transfer` for send, send_from, transfer and transfer_from
+pub fn transfer_event(
+    action: &str,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+    tax_category: TaxCategory,
+    tax_amount: Option<Uint128>,
+    tax_collector: Option<&Addr>,
+) -> Event {
+    let event = Event::new("cremation_transfer")
+        .add_attribute("action", action)
+        .add_attribute("from", from)
+        .add_attribute("to", to)
+        .add_attribute("amount", amount)
+        .add_attribute("tax_category", tax_category.to_string())
+        .add_attribute("tax_amount", tax_amount.unwrap_or_default());
+    match tax_collector {
+        Some(collector) => event.add_attribute("tax_collector", collector),
+        None => event,
+    }
+}
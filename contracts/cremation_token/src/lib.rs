@@ -1,5 +1,7 @@
 #[allow(unused_imports)]
-use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult,
+};
 use cw20_base::ContractError;
 use msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
@@ -7,6 +9,7 @@ pub mod contract;
 pub mod helper;
 pub mod msg;
 pub mod state;
+pub mod swap;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
@@ -38,5 +41,10 @@ pub fn execute(
     contract::execute(deps, env, info, msg)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    contract::reply(deps, env, msg)
+}
+
 #[cfg(test)]
 mod testing;
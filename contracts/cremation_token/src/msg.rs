@@ -1,9 +1,11 @@
+use std::fmt;
+
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 use cw20::{Expiration, Logo};
 use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
 
-use crate::state::{FractionFormat, TaxInfo};
+use crate::state::{EcosystemRole, FractionFormat, SellLimit, TaxInfo, VestingSchedule};
 
 pub use classic_terraswap::asset::AssetInfo;
 #[cw_serde]
@@ -15,6 +17,15 @@ pub enum Dex {
     Terraport,
 }
 
+impl fmt::Display for Dex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dex::Terraswap => write!(f, "terraswap"),
+            Dex::Terraport => write!(f, "terraport"),
+        }
+    }
+}
+
 #[cw_serde]
 pub enum SwapOperation {
     NativeSwap {
@@ -39,6 +50,35 @@ pub enum SwapOperation {
     },
 }
 
+/// DEX a swap hop is executed on, mapping to a `SwapOperation` variant
+#[cw_serde]
+pub enum SwapDex {
+    Native,
+    Terraswap,
+    Terraport,
+    Loop,
+    Astroport,
+}
+
+impl fmt::Display for SwapDex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SwapDex::Native => write!(f, "native"),
+            SwapDex::Terraswap => write!(f, "terraswap"),
+            SwapDex::Terraport => write!(f, "terraport"),
+            SwapDex::Loop => write!(f, "loop"),
+            SwapDex::Astroport => write!(f, "astroport"),
+        }
+    }
+}
+
+/// Swaps the previous asset of a swap path into `ask_asset_info` on `dex`
+#[cw_serde]
+pub struct SwapHop {
+    pub dex: SwapDex,
+    pub ask_asset_info: AssetInfo,
+}
+
 #[cw_serde]
 pub enum RouterExecuteMsg {
     /// Execute multiple BuyOperation
@@ -50,6 +90,34 @@ pub enum RouterExecuteMsg {
     },
 }
 
+#[cw_serde]
+pub enum BurningExecuteMsg {
+    /// Rewards of the burn go to `recipient`, or the sender if None
+    Burn { recipient: Option<String> },
+}
+
+/// Sent to registered hook contracts after a watched balance changes
+#[cw_serde]
+pub enum BalanceHookMsg {
+    BalanceChangedHook {
+        address: String,
+        old: Uint128,
+        new: Uint128,
+    },
+}
+
+/// Payload signed by the owner of a `Permit`, hashed with sha256 over its JSON encoding
+#[cw_serde]
+pub struct PermitPayload {
+    pub chain_id: String,
+    pub contract: String,
+    pub owner: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub owner: String,
@@ -80,6 +148,15 @@ pub enum ExecuteMsg {
     UpdateCollectTaxAddress {
         new_collect_tax_addr: String,
     },
+    /// Routes the swapped collected tax to the burning contract, or back to the collector if None
+    SetBurningContract {
+        burning_contract: Option<String>,
+    },
+    /// Sends the native `denom` held by this contract, like the send tax left by collected tax burns, to recipient
+    Sweep {
+        denom: String,
+        recipient: String,
+    },
     UpdateTaxInfo {
         buy_tax: Option<FractionFormat>,
         sell_tax: Option<FractionFormat>,
@@ -89,6 +166,57 @@ pub enum ExecuteMsg {
         address: String,
         tax_free: bool,
     },
+    /// Registers an ecosystem contract, exempting it from tax in the directions of its role
+    RegisterEcosystemContract {
+        address: String,
+        role: EcosystemRole,
+    },
+    DeregisterEcosystemContract {
+        address: String,
+    },
+    /// Overrides the tax info for a registered pair, or removes the override if None
+    SetPairTaxInfo {
+        pair_address: String,
+        tax_info: Option<TaxInfo>,
+    },
+    /// Caps sells through registered pairs within a rolling window, or removes the cap if None
+    SetSellLimit {
+        sell_limit: Option<SellLimit>,
+    },
+    /// Sets the allowance of spender from owner with a signature of the owner over `PermitPayload`
+    Permit {
+        owner: String,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+        pubkey: Binary,
+    },
+    /// Moves amount from the owner to address, locked under a vesting schedule.
+    /// start_time defaults to the current block time, cliff and duration are in seconds from it.
+    CreateVesting {
+        address: String,
+        amount: Uint128,
+        start_time: Option<u64>,
+        cliff: u64,
+        duration: u64,
+        revocable: bool,
+    },
+    /// Returns the still locked tokens of a revocable schedule to the owner
+    RevokeVesting {
+        address: String,
+    },
+    /// Registers a hook contract for the given addresses, or for every address if None.
+    /// Each notification runs with `gas_limit`, or DEFAULT_BALANCE_HOOK_GAS_LIMIT if None
+    AddBalanceHook {
+        contract: String,
+        addresses: Option<Vec<String>>,
+        gas_limit: Option<u64>,
+    },
+    RemoveBalanceHook {
+        contract: String,
+    },
 
     // ======= Existed executes from cw20-base =======
     /// Transfer is a base message to move tokens to another account without triggering actions
@@ -185,6 +313,8 @@ pub enum QueryMsg {
     Owner {},
     #[returns(CollectTaxAddressResponse)]
     CollectTaxAddress {},
+    #[returns(BurningContractResponse)]
+    BurningContract {},
     /// Returns the current tax info of the contract.
     /// - buy_tax: Tax rate for buy
     /// - sell_tax: Tax rate for sell
@@ -193,6 +323,31 @@ pub enum QueryMsg {
     TaxInfo {},
     #[returns(TaxFreeAddressResponse)]
     TaxFreeAddress { address: String },
+    /// Returns the registered ecosystem contracts, optionally only those with the given role.
+    #[returns(EcosystemContractsResponse)]
+    EcosystemContracts { role: Option<EcosystemRole> },
+    /// Returns every pair with overridden tax info.
+    #[returns(PairTaxInfosResponse)]
+    PairTaxInfos {},
+    /// Returns the tax info applied to trades against the given pair,
+    /// which is the global tax info unless the pair has an override.
+    #[returns(TaxInfoResponse)]
+    AppliedTaxInfo { pair_address: String },
+    #[returns(SellLimitResponse)]
+    SellLimit {},
+    /// Returns how much the given address can still sell in the rolling window and when its
+    /// oldest counted sell leaves it, None if its sells are not capped.
+    #[returns(SellAllowanceResponse)]
+    SellAllowance { address: String },
+    /// Returns the vesting schedule of the address with its vested and locked amounts.
+    #[returns(VestingInfoResponse)]
+    VestingInfo { address: String },
+    /// Returns the nonce the next permit of the owner must be signed with.
+    #[returns(PermitNonceResponse)]
+    PermitNonce { owner: String },
+    /// Returns the registered balance hook contracts and their watched addresses.
+    #[returns(BalanceHooksResponse)]
+    BalanceHooks {},
 
     // ======= Existed queries from cw20-base =======
     /// Returns the current balance of the given address, 0 if unset.
@@ -259,6 +414,53 @@ pub struct TaxInfoResponse {
     pub transfer_tax: Decimal,
 }
 
+#[cw_serde]
+pub struct PairTaxInfoResponse {
+    pub pair_address: Addr,
+    pub tax_info: TaxInfoResponse,
+}
+
+#[cw_serde]
+pub struct PairTaxInfosResponse {
+    pub pairs: Vec<PairTaxInfoResponse>,
+}
+
+#[cw_serde]
+pub struct SellLimitResponse {
+    pub sell_limit: Option<SellLimit>,
+}
+
+#[cw_serde]
+pub struct SellAllowanceResponse {
+    pub remaining: Option<Uint128>,
+    pub reset_time: Option<u64>,
+}
+
+#[cw_serde]
+pub struct VestingInfoResponse {
+    pub schedule: Option<VestingSchedule>,
+    pub vested: Uint128,
+    pub locked: Uint128,
+    // cliff end before the cliff, next second during the linear period
+    pub next_unlock: Option<u64>,
+}
+
+#[cw_serde]
+pub struct EcosystemContract {
+    pub address: Addr,
+    pub role: EcosystemRole,
+}
+
+#[cw_serde]
+pub struct EcosystemContractsResponse {
+    pub contracts: Vec<EcosystemContract>,
+}
+
+#[cw_serde]
+pub struct PermitNonceResponse {
+    pub nonce: u64,
+}
+
 #[cw_serde]
 pub struct OwnerResponse {
     pub owner: Addr,
@@ -269,7 +471,28 @@ pub struct CollectTaxAddressResponse {
     pub collect_tax_address: Addr,
 }
 
+#[cw_serde]
+pub struct BurningContractResponse {
+    pub burning_contract: Option<Addr>,
+}
+
 #[cw_serde]
 pub struct TaxFreeAddressResponse {
+    // exempted in both directions
     pub tax_free: bool,
+    // exempted when receiving and when sending, from the tax free list or the ecosystem role
+    pub incoming: bool,
+    pub outgoing: bool,
+}
+
+#[cw_serde]
+pub struct BalanceHookInfo {
+    pub contract: Addr,
+    pub addresses: Option<Vec<Addr>>,
+    pub gas_limit: u64,
+}
+
+#[cw_serde]
+pub struct BalanceHooksResponse {
+    pub hooks: Vec<BalanceHookInfo>,
 }
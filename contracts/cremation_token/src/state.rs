@@ -1,3 +1,5 @@
+use std::fmt;
+
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
@@ -40,9 +42,133 @@ pub struct TaxInfo {
     pub transfer_tax: Option<FractionFormat>,
 }
 pub const TAX_INFO: Item<TaxInfo> = Item::new("tax_info");
+// overrides the global tax info for movements against a registered pair
+pub const PAIR_TAX_INFO: Map<Addr, TaxInfo> = Map::new("pair_tax_info");
 
 pub const COLLECT_TAX_ADDRESS: Item<Addr> = Item::new("collect_tax_address");
+// burns the swapped collected tax through the burning contract instead of sending it to the collector
+pub const BURNING_CONTRACT: Item<Addr> = Item::new("burning_contract");
+// uluna balance of this contract before swapping collected tax, to measure the swapped amount
+pub const PRE_TAX_SWAP_BALANCE: Item<Uint128> = Item::new("pre_tax_swap_balance");
 pub const TAX_FREE_ADDRESSES: Map<Addr, bool> = Map::new("tax_free_addresses");
 
+#[cw_serde]
+pub enum EcosystemRole {
+    Stake,
+    Burn,
+    Lock,
+    Proxy,
+    Treasury,
+}
+
+impl EcosystemRole {
+    // deposits into stake, lock, burn and treasury contracts are credited at face value
+    pub fn exempts_incoming(&self) -> bool {
+        !matches!(self, EcosystemRole::Proxy)
+    }
+
+    // stake and lock withdrawals and proxy deliveries are not taxed twice
+    pub fn exempts_outgoing(&self) -> bool {
+        matches!(
+            self,
+            EcosystemRole::Stake | EcosystemRole::Lock | EcosystemRole::Proxy
+        )
+    }
+}
+
+impl fmt::Display for EcosystemRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EcosystemRole::Stake => write!(f, "stake"),
+            EcosystemRole::Burn => write!(f, "burn"),
+            EcosystemRole::Lock => write!(f, "lock"),
+            EcosystemRole::Proxy => write!(f, "proxy"),
+            EcosystemRole::Treasury => write!(f, "treasury"),
+        }
+    }
+}
+pub const ECOSYSTEM_CONTRACTS: Map<Addr, EcosystemRole> = Map::new("ecosystem_contracts");
+
+#[cw_serde]
+pub struct SellLimit {
+    pub total: Option<Uint128>,
+    pub per_address: Option<Uint128>,
+    pub duration: u64,
+}
+pub const SELL_LIMIT: Item<SellLimit> = Item::new("sell_limit");
+
+// sells of the rolling window, grouped into buckets opened by the first sell they hold
+#[cw_serde]
+#[derive(Default)]
+pub struct SoldWindow {
+    pub buckets: Vec<SoldBucket>,
+}
+
+#[cw_serde]
+pub struct SoldBucket {
+    pub start: u64,
+    pub amount: Uint128,
+}
+
+impl SoldWindow {
+    pub fn amount(&self) -> Uint128 {
+        self.buckets.iter().map(|bucket| bucket.amount).sum()
+    }
+
+    // the time the oldest counted sell leaves the window
+    pub fn next_release(&self, duration: u64) -> Option<u64> {
+        self.buckets
+            .iter()
+            .map(|bucket| bucket.start + duration)
+            .min()
+    }
+}
+
+pub const TOTAL_SOLD: Item<SoldWindow> = Item::new("total_sold_window");
+pub const SOLD_BY_ADDRESS: Map<Addr, SoldWindow> = Map::new("sold_window_by_address");
+
+// tokens unlock linearly from `start_time` to `start_time + duration`, nothing before the cliff
+#[cw_serde]
+pub struct VestingSchedule {
+    pub amount: Uint128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub revocable: bool,
+}
+
+impl VestingSchedule {
+    pub fn vested(&self, now: u64) -> Uint128 {
+        if now < self.start_time + self.cliff {
+            Uint128::zero()
+        } else if now >= self.end_time() {
+            self.amount
+        } else {
+            self.amount
+                .multiply_ratio(now - self.start_time, self.duration)
+        }
+    }
+
+    pub fn locked(&self, now: u64) -> Uint128 {
+        self.amount - self.vested(now)
+    }
+
+    pub fn end_time(&self) -> u64 {
+        self.start_time + self.duration
+    }
+}
+pub const VESTING_SCHEDULES: Map<Addr, VestingSchedule> = Map::new("vesting_schedules");
+
+// next nonce expected in a permit signed by the owner
+pub const PERMIT_NONCES: Map<Addr, u64> = Map::new("permit_nonces");
+
 pub const OWNER: Item<Addr> = Item::new("owner");
 pub const CREATOR: Item<Addr> = Item::new("creator");
+
+// hook contracts notified after balance changes, watching every address when `addresses` is None
+#[cw_serde]
+pub struct BalanceHook {
+    pub addresses: Option<Vec<Addr>>,
+    pub gas_limit: u64,
+}
+pub const BALANCE_HOOKS: Map<Addr, BalanceHook> = Map::new("balance_hooks");
@@ -0,0 +1,159 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Coin, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use thiserror::Error;
+
+use crate::msg::{AssetInfo, RouterExecuteMsg, SwapDex, SwapHop, SwapOperation};
+
+/// Errors of building swap runs, mapped into the contract errors of the callers
+#[derive(Error, Debug, PartialEq)]
+pub enum SwapPathError {
+    #[error("{0}")]
+    StdError(#[from] StdError),
+    #[error("Invalid Swap Paths")]
+    InvalidSwapPaths {},
+    #[error("Swap Router Not Set For {dex}")]
+    SwapRouterNotSet { dex: String },
+}
+
+/// Consecutive hops of a swap path executed by the same router
+#[cw_serde]
+pub struct SwapRun {
+    pub router: Addr,
+    pub offer_asset_info: AssetInfo,
+    pub ask_asset_info: AssetInfo,
+    pub operations: Vec<SwapOperation>,
+}
+
+/// Splits the swap paths into runs of consecutive hops sharing a router, each run swapping
+/// the output of the previous one. `router_of` returns the router set for a dex.
+pub fn create_swap_runs(
+    offer_asset: AssetInfo,
+    ask_asset: &AssetInfo,
+    swap_paths: Vec<SwapHop>,
+    router_of: impl Fn(&SwapDex) -> StdResult<Option<Addr>>,
+) -> Result<Vec<SwapRun>, SwapPathError> {
+    // the last hop must end in the ask asset
+    match swap_paths.last() {
+        Some(hop) if hop.ask_asset_info == *ask_asset => {}
+        _ => return Err(SwapPathError::InvalidSwapPaths {}),
+    }
+
+    let mut runs: Vec<SwapRun> = vec![];
+    let mut offer_asset_info = offer_asset;
+    for hop in swap_paths {
+        let router = router_of(&hop.dex)?.ok_or(SwapPathError::SwapRouterNotSet {
+            dex: hop.dex.to_string(),
+        })?;
+        let operation = swap_operation(&hop.dex, &offer_asset_info, &hop.ask_asset_info)?;
+        match runs.last_mut() {
+            Some(run) if run.router == router => {
+                run.operations.push(operation);
+                run.ask_asset_info = hop.ask_asset_info.clone();
+            }
+            _ => runs.push(SwapRun {
+                router,
+                offer_asset_info: offer_asset_info.clone(),
+                ask_asset_info: hop.ask_asset_info.clone(),
+                operations: vec![operation],
+            }),
+        }
+        offer_asset_info = hop.ask_asset_info;
+    }
+    Ok(runs)
+}
+
+fn swap_operation(
+    dex: &SwapDex,
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: &AssetInfo,
+) -> Result<SwapOperation, SwapPathError> {
+    let offer_asset_info = offer_asset_info.clone();
+    let ask_asset_info = ask_asset_info.clone();
+    let operation = match dex {
+        SwapDex::Native => match (offer_asset_info, ask_asset_info) {
+            (
+                AssetInfo::NativeToken { denom: offer_denom },
+                AssetInfo::NativeToken { denom: ask_denom },
+            ) => SwapOperation::NativeSwap {
+                offer_denom,
+                ask_denom,
+            },
+            _ => return Err(SwapPathError::InvalidSwapPaths {}),
+        },
+        SwapDex::Terraswap => SwapOperation::TerraSwap {
+            offer_asset_info,
+            ask_asset_info,
+        },
+        SwapDex::Terraport => SwapOperation::TerraPort {
+            offer_asset_info,
+            ask_asset_info,
+        },
+        SwapDex::Loop => SwapOperation::Loop {
+            offer_asset_info,
+            ask_asset_info,
+        },
+        SwapDex::Astroport => SwapOperation::Astroport {
+            offer_asset_info,
+            ask_asset_info,
+        },
+    };
+    Ok(operation)
+}
+
+/// Sends `amount` of the offer asset of a run to its router, as funds or a cw20 send.
+/// `minimum_receive` only makes sense for the last run of a swap.
+pub fn swap_run_msg(
+    run: &SwapRun,
+    amount: Uint128,
+    minimum_receive: Option<Uint128>,
+    deadline: Option<u64>,
+) -> StdResult<CosmosMsg> {
+    let swap_msg = to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+        operations: run.operations.clone(),
+        minimum_receive,
+        to: None,
+        deadline,
+    })?;
+    let msg = match &run.offer_asset_info {
+        AssetInfo::NativeToken { denom } => WasmMsg::Execute {
+            contract_addr: run.router.to_string(),
+            msg: swap_msg,
+            funds: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        },
+        AssetInfo::Token { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                contract: run.router.to_string(),
+                amount,
+                msg: swap_msg,
+            })?,
+            funds: vec![],
+        },
+    };
+    Ok(msg.into())
+}
+
+pub fn query_asset_balance(
+    querier: &QuerierWrapper,
+    asset_info: &AssetInfo,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    match asset_info {
+        AssetInfo::Token { contract_addr } => {
+            let res: Cw20BalanceResponse = querier.query_wasm_smart(
+                contract_addr,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            Ok(res.balance)
+        }
+        AssetInfo::NativeToken { denom } => Ok(querier.query_balance(address, denom)?.amount),
+    }
+}
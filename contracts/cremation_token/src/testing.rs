@@ -1,24 +1,35 @@
 use std::vec;
 
+use bech32::{ToBase32, Variant};
 use cosmwasm_std::{
     from_json,
     testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
-    Addr, Binary, CosmosMsg, Decimal, Empty, OwnedDeps, Response, Uint128, WasmMsg,
+    to_json_binary, to_json_vec, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, Empty, Event,
+    OwnedDeps, Reply, ReplyOn, Response, StdError, SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
 };
-use cw20::{Cw20Coin, TokenInfoResponse};
+use cw20::{AllowanceResponse, BalanceResponse, Cw20Coin, TokenInfoResponse};
 use cw20_base::{msg::InstantiateMsg as Cw20InstantiateMsg, ContractError};
+use k256::ecdsa::{signature::Signer, Signature, SigningKey};
 
 use crate::{
-    contract::{execute, SWAP_COLLECTED_TAX_THRESHOLD},
+    contract::{
+        execute, reply, BALANCE_HOOK_REPLY_ID, BURN_COLLECTED_TAX_REPLY_ID,
+        DEFAULT_BALANCE_HOOK_GAS_LIMIT, SWAP_COLLECTED_TAX_THRESHOLD,
+    },
     helper::{is_buy_operation, is_sell_operation},
     instantiate,
     msg::{
-        CollectTaxAddressResponse, DexConfigsResponse, ExecuteMsg, InstantiateMsg, OwnerResponse,
-        QueryMsg, TaxFreeAddressResponse, TaxInfoResponse,
+        BalanceHookMsg, BalanceHooksResponse, BurningContractResponse, BurningExecuteMsg,
+        CollectTaxAddressResponse, DexConfigsResponse, EcosystemContract,
+        EcosystemContractsResponse, ExecuteMsg, InstantiateMsg, OwnerResponse,
+        PairTaxInfosResponse, PermitNonceResponse, PermitPayload, QueryMsg, SellAllowanceResponse,
+        TaxFreeAddressResponse, TaxInfoResponse, VestingInfoResponse,
     },
     query,
-    state::{DexConfigs, FractionFormat, TaxInfo},
+    state::{DexConfigs, EcosystemRole, FractionFormat, SellLimit, TaxInfo},
 };
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 use self::helpers::get_dex_configs;
 
@@ -119,6 +130,16 @@ mod helpers {
         let balance_res: cw20::BalanceResponse = from_json(&balance_query).unwrap();
         balance_res.balance
     }
+
+    pub fn event_attribute(event: &Event, key: &str) -> String {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == key)
+            .unwrap()
+            .value
+            .clone()
+    }
 }
 
 #[test]
@@ -448,6 +469,160 @@ fn set_tax_free_address() {
     assert_eq!(response.tax_free, false);
 }
 
+#[test]
+fn unset_tax_free_address_is_taxed() {
+    let mut deps = mock_dependencies();
+    let owner = "owner";
+    let user = "user";
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: Some(FractionFormat {
+                numerator: Uint128::new(10),
+                denominator: Uint128::new(100),
+            }),
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![Cw20Coin {
+            address: user.to_string(),
+            amount: Uint128::new(1000),
+        }]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    let set_tax_free = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>,
+                        tax_free: bool| {
+        let msg = ExecuteMsg::SetTaxFreeAddress {
+            address: user.to_string(),
+            tax_free,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(owner, &[]), msg).unwrap();
+    };
+    let transfer = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>| {
+        let msg = ExecuteMsg::Transfer {
+            recipient: "recipient".to_string(),
+            amount: Uint128::new(100),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(user, &[]), msg).unwrap();
+        helpers::event_attribute(&res.events[0], "tax_category")
+    };
+
+    set_tax_free(&mut deps, true);
+    assert_eq!(transfer(&mut deps), "none");
+
+    // the address stays in the map with a false flag and is taxed again
+    set_tax_free(&mut deps, false);
+    assert_eq!(transfer(&mut deps), "transfer");
+}
+
+#[test]
+fn emit_events_for_admin_actions() {
+    let mut deps = mock_dependencies();
+    let owner = "owner";
+    let tax_rate = FractionFormat {
+        numerator: Uint128::new(8),
+        denominator: Uint128::new(100),
+    };
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: Some(tax_rate.clone()),
+            sell_tax: None,
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // update tax info
+    let info = mock_info(owner, &[]);
+    let msg = ExecuteMsg::UpdateTaxInfo {
+        buy_tax: Some(tax_rate.clone()),
+        sell_tax: Some(FractionFormat {
+            numerator: Uint128::new(10),
+            denominator: Uint128::new(100),
+        }),
+        transfer_tax: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.events.len(), 1);
+    let event = &res.events[0];
+    assert_eq!(event.ty, "cremation_update_tax_info");
+    assert_eq!(
+        helpers::event_attribute(event, "old_sell_tax"),
+        Decimal::zero().to_string()
+    );
+    assert_eq!(
+        helpers::event_attribute(event, "new_sell_tax"),
+        Decimal::percent(10).to_string()
+    );
+    assert_eq!(
+        helpers::event_attribute(event, "new_buy_tax"),
+        Decimal::percent(8).to_string()
+    );
+
+    // add new pairs
+    let info = mock_info(owner, &[]);
+    let msg = ExecuteMsg::AddNewPairs {
+        dex: crate::msg::Dex::Terraswap,
+        pair_addresses: vec!["terraswap_pair_new".to_string()],
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "cremation_add_new_pairs");
+    assert_eq!(helpers::event_attribute(event, "dex"), "terraswap");
+    assert_eq!(
+        helpers::event_attribute(event, "old_pairs"),
+        "terraswap_pair"
+    );
+    assert_eq!(
+        helpers::event_attribute(event, "new_pairs"),
+        "terraswap_pair,terraswap_pair_new"
+    );
+
+    // remove pair
+    let info = mock_info(owner, &[]);
+    let msg = ExecuteMsg::RemovePair {
+        dex: crate::msg::Dex::Terraport,
+        pair_address: "terraport_pair".to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "cremation_remove_pair");
+    assert_eq!(
+        helpers::event_attribute(event, "removed_pair"),
+        "terraport_pair"
+    );
+    assert_eq!(
+        helpers::event_attribute(event, "new_pairs"),
+        "terraport_pair2"
+    );
+
+    // set tax free address
+    let info = mock_info(owner, &[]);
+    let msg = ExecuteMsg::SetTaxFreeAddress {
+        address: "tax_free_addr".to_string(),
+        tax_free: true,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "cremation_set_tax_free_address");
+    assert_eq!(helpers::event_attribute(event, "old_tax_free"), "false");
+    assert_eq!(helpers::event_attribute(event, "new_tax_free"), "true");
+
+    // update owner
+    let info = mock_info(owner, &[]);
+    let msg = ExecuteMsg::UpdateOwner {
+        new_owner: "new_owner".to_string(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "cremation_update_owner");
+    assert_eq!(helpers::event_attribute(event, "old_owner"), owner);
+    assert_eq!(helpers::event_attribute(event, "new_owner"), "new_owner");
+}
+
 // ======= test tax =======
 // - send
 // - send from
@@ -976,6 +1151,67 @@ fn collect_transfer_tax_when_execute_transfer_from() {
     }
 }
 
+#[test]
+fn emit_transfer_event_with_tax() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner-tax-free");
+    let seller = Addr::unchecked("seller");
+    let user = Addr::unchecked("user");
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: Some(FractionFormat {
+                numerator: Uint128::new(10),
+                denominator: Uint128::new(100),
+            }),
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![Cw20Coin {
+            address: seller.to_string(),
+            amount: Uint128::new(1000),
+        }]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // sell to terraswap pair
+    let msg = ExecuteMsg::Send {
+        contract: "terraswap_pair".to_string(),
+        amount: Uint128::new(100),
+        msg: Binary::default(),
+    };
+    let info = mock_info(seller.as_str(), &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let event = res
+        .events
+        .iter()
+        .find(|event| event.ty == "cremation_transfer")
+        .unwrap();
+    assert_eq!(helpers::event_attribute(event, "action"), "send");
+    assert_eq!(helpers::event_attribute(event, "tax_category"), "sell");
+    assert_eq!(helpers::event_attribute(event, "tax_amount"), "10");
+    assert_eq!(
+        helpers::event_attribute(event, "tax_collector"),
+        owner.to_string()
+    );
+
+    // transfer without transfer tax
+    let msg = ExecuteMsg::Transfer {
+        recipient: user.to_string(),
+        amount: Uint128::new(100),
+    };
+    let info = mock_info(seller.as_str(), &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let event = &res.events[0];
+    assert_eq!(event.ty, "cremation_transfer");
+    assert_eq!(helpers::event_attribute(event, "tax_category"), "none");
+    assert_eq!(helpers::event_attribute(event, "tax_amount"), "0");
+    assert!(!event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "tax_collector"));
+}
+
 #[test]
 fn trigger_auto_swap_collected_tax() {
     let mut deps = mock_dependencies();
@@ -1046,6 +1282,540 @@ fn trigger_auto_swap_collected_tax() {
     }
 }
 
+#[test]
+fn apply_pair_tax_info() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner-tax-free");
+    let user = Addr::unchecked("user");
+    let tax_rate = FractionFormat {
+        numerator: Uint128::new(8),
+        denominator: Uint128::new(100),
+    };
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: Some(tax_rate.clone()),
+            sell_tax: Some(tax_rate.clone()),
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![
+            Cw20Coin {
+                address: user.to_string(),
+                amount: Uint128::new(1000),
+            },
+            Cw20Coin {
+                address: "terraport_pair2".to_string(),
+                amount: Uint128::new(1000),
+            },
+        ]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // fail to override unregistered pair
+    let info = mock_info(owner.as_str(), &[]);
+    let msg = ExecuteMsg::SetPairTaxInfo {
+        pair_address: "unknown_pair".to_string(),
+        tax_info: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+
+    // 20% sell tax on terraswap pair, zero tax on terraport pair2
+    let msg = ExecuteMsg::SetPairTaxInfo {
+        pair_address: "terraswap_pair".to_string(),
+        tax_info: Some(TaxInfo {
+            buy_tax: Some(tax_rate.clone()),
+            sell_tax: Some(FractionFormat {
+                numerator: Uint128::new(20),
+                denominator: Uint128::new(100),
+            }),
+            transfer_tax: None,
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::SetPairTaxInfo {
+        pair_address: "terraport_pair2".to_string(),
+        tax_info: Some(TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: None,
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let pair_tax_infos_query = query(deps.as_ref(), mock_env(), QueryMsg::PairTaxInfos {}).unwrap();
+    let pair_tax_infos_res: PairTaxInfosResponse = from_json(&pair_tax_infos_query).unwrap();
+    assert_eq!(pair_tax_infos_res.pairs.len(), 2);
+
+    let applied_query = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AppliedTaxInfo {
+            pair_address: "terraswap_pair".to_string(),
+        },
+    )
+    .unwrap();
+    let applied_res: TaxInfoResponse = from_json(&applied_query).unwrap();
+    assert_eq!(applied_res.sell_tax, Decimal::percent(20));
+    let applied_query = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AppliedTaxInfo {
+            pair_address: "terraport_pair".to_string(),
+        },
+    )
+    .unwrap();
+    let applied_res: TaxInfoResponse = from_json(&applied_query).unwrap();
+    assert_eq!(applied_res.sell_tax, Decimal::percent(8));
+
+    // sell to terraswap pair uses the override
+    let msg = ExecuteMsg::Send {
+        contract: "terraswap_pair".to_string(),
+        amount: Uint128::new(100),
+        msg: Binary::default(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+    let tax = res
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "cw20_tax_amount")
+        .unwrap();
+    assert_eq!(tax.value, "20");
+
+    // sell to terraport pair keeps the global rate
+    let msg = ExecuteMsg::Send {
+        contract: "terraport_pair".to_string(),
+        amount: Uint128::new(100),
+        msg: Binary::default(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+    let tax = res
+        .attributes
+        .iter()
+        .find(|attr| attr.key == "cw20_tax_amount")
+        .unwrap();
+    assert_eq!(tax.value, "8");
+
+    // buy from terraport pair2 is tax free
+    let msg = ExecuteMsg::Transfer {
+        recipient: user.to_string(),
+        amount: Uint128::new(100),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("terraport_pair2", &[]),
+        msg,
+    )
+    .unwrap();
+    assert!(!res
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "cw20_tax_amount"));
+
+    // removing the pair drops its override
+    let msg = ExecuteMsg::RemovePair {
+        dex: crate::msg::Dex::Terraport,
+        pair_address: "terraport_pair2".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let pair_tax_infos_query = query(deps.as_ref(), mock_env(), QueryMsg::PairTaxInfos {}).unwrap();
+    let pair_tax_infos_res: PairTaxInfosResponse = from_json(&pair_tax_infos_query).unwrap();
+    assert_eq!(pair_tax_infos_res.pairs.len(), 1);
+}
+
+#[test]
+fn enforce_sell_limit() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner-tax-free");
+    let users = [
+        Addr::unchecked("user1"),
+        Addr::unchecked("user2"),
+        Addr::unchecked("user3"),
+    ];
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(
+            users
+                .iter()
+                .map(|user| Cw20Coin {
+                    address: user.to_string(),
+                    amount: Uint128::new(1000),
+                })
+                .collect(),
+        ),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    let duration = 86400;
+    let msg = ExecuteMsg::SetSellLimit {
+        sell_limit: Some(SellLimit {
+            total: Some(Uint128::new(250)),
+            per_address: Some(Uint128::new(150)),
+            duration,
+        }),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("non_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+
+    let sell_msg = ExecuteMsg::Send {
+        contract: "terraswap_pair".to_string(),
+        amount: Uint128::new(100),
+        msg: Binary::default(),
+    };
+
+    // user1 sells up to the per address limit
+    let info = mock_info(users[0].as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info.clone(), sell_msg.clone()).unwrap();
+    execute(deps.as_mut(), mock_env(), info.clone(), sell_msg.clone()).unwrap_err();
+
+    let allowance_query = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SellAllowance {
+            address: users[0].to_string(),
+        },
+    )
+    .unwrap();
+    let allowance_res: SellAllowanceResponse = from_json(&allowance_query).unwrap();
+    assert_eq!(allowance_res.remaining, Some(Uint128::new(50)));
+    assert_eq!(
+        allowance_res.reset_time,
+        Some(mock_env().block.time.seconds() + duration)
+    );
+
+    // transfers between users are not counted
+    let msg = ExecuteMsg::Transfer {
+        recipient: users[1].to_string(),
+        amount: Uint128::new(200),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    // user2 sells, user3 then hits the total limit
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(users[1].as_str(), &[]),
+        sell_msg.clone(),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(users[2].as_str(), &[]),
+        sell_msg.clone(),
+    )
+    .unwrap_err();
+
+    // tax free owner is not capped
+    let allowance_query = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SellAllowance {
+            address: owner.to_string(),
+        },
+    )
+    .unwrap();
+    let allowance_res: SellAllowanceResponse = from_json(&allowance_query).unwrap();
+    assert_eq!(allowance_res.remaining, None);
+
+    // sells leave the window one duration after they happened
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(duration);
+    execute(deps.as_mut(), env.clone(), info.clone(), sell_msg.clone()).unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(users[2].as_str(), &[]),
+        sell_msg,
+    )
+    .unwrap();
+
+    // the window rolls, so the sell of half a window ago still counts
+    env.block.time = env.block.time.plus_seconds(duration / 2);
+    let sell_msg = |amount: u128| ExecuteMsg::Send {
+        contract: "terraswap_pair".to_string(),
+        amount: Uint128::new(amount),
+        msg: Binary::default(),
+    };
+    execute(deps.as_mut(), env.clone(), info.clone(), sell_msg(50)).unwrap();
+    execute(deps.as_mut(), env.clone(), info.clone(), sell_msg(1)).unwrap_err();
+
+    env.block.time = env.block.time.plus_seconds(duration / 2);
+    let allowance_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SellAllowance {
+            address: users[0].to_string(),
+        },
+    )
+    .unwrap();
+    let allowance_res: SellAllowanceResponse = from_json(&allowance_query).unwrap();
+    assert_eq!(allowance_res.remaining, Some(Uint128::new(100)));
+    assert_eq!(
+        allowance_res.reset_time,
+        Some(env.block.time.seconds() + duration / 2)
+    );
+    execute(deps.as_mut(), env.clone(), info.clone(), sell_msg(100)).unwrap();
+    execute(deps.as_mut(), env, info, sell_msg(1)).unwrap_err();
+}
+
+#[test]
+fn enforce_sell_limit_through_proxy() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    let proxy = Addr::unchecked("proxy");
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![Cw20Coin {
+            address: user.to_string(),
+            amount: Uint128::new(1000),
+        }]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    let owner_info = mock_info(owner.as_str(), &[]);
+    let msg = ExecuteMsg::RegisterEcosystemContract {
+        address: proxy.to_string(),
+        role: EcosystemRole::Proxy,
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::SetSellLimit {
+        sell_limit: Some(SellLimit {
+            total: None,
+            per_address: Some(Uint128::new(150)),
+            duration: 86400,
+        }),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+
+    // the user's send into the proxy counts as the sell
+    let sell_msg = ExecuteMsg::Send {
+        contract: proxy.to_string(),
+        amount: Uint128::new(100),
+        msg: Binary::default(),
+    };
+    let info = mock_info(user.as_str(), &[]);
+    execute(deps.as_mut(), mock_env(), info.clone(), sell_msg.clone()).unwrap();
+
+    // the proxy delivering to the pair is not counted again
+    let msg = ExecuteMsg::Send {
+        contract: "terraswap_pair".to_string(),
+        amount: Uint128::new(100),
+        msg: Binary::default(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(proxy.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+
+    let err = execute(deps.as_mut(), mock_env(), info, sell_msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Exceed sell limit per address"))
+    );
+}
+
+#[test]
+fn notify_balance_hooks() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner-tax-free");
+    let user1 = Addr::unchecked("user1");
+    let user2 = Addr::unchecked("user2");
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: Some(FractionFormat {
+                numerator: Uint128::new(10),
+                denominator: Uint128::new(100),
+            }),
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![Cw20Coin {
+            address: user1.to_string(),
+            amount: Uint128::new(1000),
+        }]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // fail to add hook with non-owner
+    let msg = ExecuteMsg::AddBalanceHook {
+        contract: "global_hook".to_string(),
+        addresses: None,
+        gas_limit: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("non_owner", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // add a global hook and a hook watching user2 only
+    let info = mock_info(owner.as_str(), &[]);
+    let msg = ExecuteMsg::AddBalanceHook {
+        contract: "global_hook".to_string(),
+        addresses: None,
+        gas_limit: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddBalanceHook {
+        contract: "user2_hook".to_string(),
+        addresses: Some(vec![user2.to_string()]),
+        gas_limit: Some(200_000),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let hooks_query = query(deps.as_ref(), mock_env(), QueryMsg::BalanceHooks {}).unwrap();
+    let hooks_res: BalanceHooksResponse = from_json(&hooks_query).unwrap();
+    assert_eq!(hooks_res.hooks.len(), 2);
+    assert_eq!(hooks_res.hooks[0].gas_limit, DEFAULT_BALANCE_HOOK_GAS_LIMIT);
+    assert_eq!(hooks_res.hooks[1].gas_limit, 200_000);
+
+    // transfer with tax changes user1, user2 and collector balances
+    let msg = ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::new(100),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user1.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+    let hook_msgs = res
+        .messages
+        .iter()
+        .map(|sub_msg| {
+            assert_eq!(sub_msg.id, BALANCE_HOOK_REPLY_ID);
+            assert_eq!(sub_msg.reply_on, ReplyOn::Error);
+            match &sub_msg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr, msg, ..
+                }) => {
+                    let gas_limit = match contract_addr.as_str() {
+                        "user2_hook" => 200_000,
+                        _ => DEFAULT_BALANCE_HOOK_GAS_LIMIT,
+                    };
+                    assert_eq!(sub_msg.gas_limit, Some(gas_limit));
+                    (contract_addr.clone(), from_json(msg).unwrap())
+                }
+                _ => panic!("Unexpected message"),
+            }
+        })
+        .collect::<Vec<(String, BalanceHookMsg)>>();
+    assert_eq!(hook_msgs.len(), 4);
+    assert!(hook_msgs.contains(&(
+        "global_hook".to_string(),
+        BalanceHookMsg::BalanceChangedHook {
+            address: user1.to_string(),
+            old: Uint128::new(1000),
+            new: Uint128::new(900),
+        }
+    )));
+    assert!(hook_msgs.contains(&(
+        "user2_hook".to_string(),
+        BalanceHookMsg::BalanceChangedHook {
+            address: user2.to_string(),
+            old: Uint128::zero(),
+            new: Uint128::new(90),
+        }
+    )));
+    assert!(hook_msgs.contains(&(
+        "global_hook".to_string(),
+        BalanceHookMsg::BalanceChangedHook {
+            address: owner.to_string(),
+            old: Uint128::zero(),
+            new: Uint128::new(10),
+        }
+    )));
+
+    // a hook running out of gas is swallowed in reply and the transfer stays applied
+    let reply_msg = Reply {
+        id: BALANCE_HOOK_REPLY_ID,
+        result: SubMsgResult::Err("out of gas in location: wasm contract".to_string()),
+    };
+    let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+    assert!(res.messages.is_empty());
+    assert_eq!(res.events[0].ty, "cremation_balance_hook_failed");
+    assert_eq!(
+        res.events[0].attributes[0].value,
+        "out of gas in location: wasm contract"
+    );
+    let balance_query = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Balance {
+            address: user2.to_string(),
+        },
+    )
+    .unwrap();
+    let balance_res: BalanceResponse = from_json(&balance_query).unwrap();
+    assert_eq!(balance_res.balance, Uint128::new(90));
+
+    // zero gas limit is rejected
+    let msg = ExecuteMsg::AddBalanceHook {
+        contract: "zero_gas_hook".to_string(),
+        addresses: None,
+        gas_limit: Some(0),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+
+    // removed hook is not notified anymore
+    let msg = ExecuteMsg::RemoveBalanceHook {
+        contract: "global_hook".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::Transfer {
+        recipient: user2.to_string(),
+        amount: Uint128::new(100),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(user1.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+}
+
 // test add new pairs after migrate
 #[test]
 fn add_new_pairs_after_migrate() {
@@ -1092,3 +1862,527 @@ fn add_new_pairs_after_migrate() {
         assert!(config_res.terraswap_pairs.contains(&Addr::unchecked(pair)));
     }
 }
+
+#[test]
+fn set_allowance_with_permit() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let init_msg = InstantiateMsg {
+        owner: "owner".to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+    let pubkey = Binary::from(
+        signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let hash = Ripemd160::digest(Sha256::digest(pubkey.as_slice()));
+    let owner = bech32::encode("terra", hash.to_base32(), Variant::Bech32).unwrap();
+    let spender = "spender".to_string();
+
+    let sign = |nonce: u64, amount: Uint128| -> Binary {
+        let payload = PermitPayload {
+            chain_id: env.block.chain_id.clone(),
+            contract: env.contract.address.to_string(),
+            owner: owner.clone(),
+            spender: spender.clone(),
+            amount,
+            expires: None,
+            nonce,
+        };
+        let signature: Signature = signing_key.sign(&to_json_vec(&payload).unwrap());
+        Binary::from(signature.to_bytes().as_slice())
+    };
+    let permit = |nonce: u64, amount: Uint128, signature: Binary| ExecuteMsg::Permit {
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        expires: None,
+        nonce,
+        signature,
+        pubkey: pubkey.clone(),
+    };
+    let info = mock_info("relayer", &[]);
+
+    // fail with a signature over another amount
+    let msg = permit(0, Uint128::new(200), sign(0, Uint128::new(100)));
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Invalid permit signature").into()
+    );
+
+    // fail with a pubkey of another owner
+    let msg = ExecuteMsg::Permit {
+        owner: "terra1other".to_string(),
+        spender: spender.clone(),
+        amount: Uint128::new(100),
+        expires: None,
+        nonce: 0,
+        signature: sign(0, Uint128::new(100)),
+        pubkey: pubkey.clone(),
+    };
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Pubkey does not match owner").into()
+    );
+
+    // set allowance with a valid permit
+    let msg = permit(0, Uint128::new(100), sign(0, Uint128::new(100)));
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.attributes[0].value, "permit");
+    let allowance_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Allowance {
+            owner: owner.clone(),
+            spender: spender.clone(),
+        },
+    )
+    .unwrap();
+    let allowance_res: AllowanceResponse = from_json(&allowance_query).unwrap();
+    assert_eq!(allowance_res.allowance, Uint128::new(100));
+
+    let nonce_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::PermitNonce {
+            owner: owner.clone(),
+        },
+    )
+    .unwrap();
+    let nonce_res: PermitNonceResponse = from_json(&nonce_query).unwrap();
+    assert_eq!(nonce_res.nonce, 1);
+
+    // fail to replay the permit
+    let err = execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("Invalid permit nonce").into());
+
+    // overwrite allowance with the next nonce
+    let msg = permit(1, Uint128::new(30), sign(1, Uint128::new(30)));
+    execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+    let allowance_query =
+        query(deps.as_ref(), env, QueryMsg::Allowance { owner, spender }).unwrap();
+    let allowance_res: AllowanceResponse = from_json(&allowance_query).unwrap();
+    assert_eq!(allowance_res.allowance, Uint128::new(30));
+}
+
+#[test]
+fn lock_unvested_tokens() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let owner = Addr::unchecked("owner");
+    let team = Addr::unchecked("team");
+    let user = Addr::unchecked("user");
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![Cw20Coin {
+            address: owner.to_string(),
+            amount: Uint128::new(10000),
+        }]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // fail to create vesting with non-owner
+    let msg = ExecuteMsg::CreateVesting {
+        address: team.to_string(),
+        amount: Uint128::new(1000),
+        start_time: None,
+        cliff: 100,
+        duration: 1000,
+        revocable: true,
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("non_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let owner_info = mock_info(owner.as_str(), &[]);
+    execute(deps.as_mut(), env.clone(), owner_info.clone(), msg).unwrap();
+    let vesting_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::VestingInfo {
+            address: team.to_string(),
+        },
+    )
+    .unwrap();
+    let vesting_res: VestingInfoResponse = from_json(&vesting_query).unwrap();
+    assert_eq!(vesting_res.vested, Uint128::zero());
+    assert_eq!(vesting_res.locked, Uint128::new(1000));
+    assert_eq!(
+        vesting_res.next_unlock,
+        Some(env.block.time.seconds() + 100)
+    );
+
+    // fail to transfer or burn locked tokens before the cliff
+    let team_info = mock_info(team.as_str(), &[]);
+    let msg = ExecuteMsg::Transfer {
+        recipient: user.to_string(),
+        amount: Uint128::new(1),
+    };
+    let err = execute(deps.as_mut(), env.clone(), team_info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Cannot transfer locked vesting tokens").into()
+    );
+    let msg = ExecuteMsg::Burn {
+        amount: Uint128::new(1),
+    };
+    let err = execute(deps.as_mut(), env.clone(), team_info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Cannot transfer locked vesting tokens").into()
+    );
+
+    // transfer vested tokens halfway through the linear period
+    env.block.time = env.block.time.plus_seconds(500);
+    let msg = ExecuteMsg::Transfer {
+        recipient: user.to_string(),
+        amount: Uint128::new(501),
+    };
+    let err = execute(deps.as_mut(), env.clone(), team_info.clone(), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("Cannot transfer locked vesting tokens").into()
+    );
+    let msg = ExecuteMsg::Transfer {
+        recipient: user.to_string(),
+        amount: Uint128::new(500),
+    };
+    execute(deps.as_mut(), env.clone(), team_info, msg).unwrap();
+
+    // revoke the locked part back to the owner
+    let msg = ExecuteMsg::RevokeVesting {
+        address: team.to_string(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), owner_info, msg).unwrap();
+    assert_eq!(
+        helpers::event_attribute(&res.events[0], "revoked_amount"),
+        "500"
+    );
+    let balance_query = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Balance {
+            address: owner.to_string(),
+        },
+    )
+    .unwrap();
+    let balance_res: BalanceResponse = from_json(&balance_query).unwrap();
+    assert_eq!(balance_res.balance, Uint128::new(9500));
+
+    let vesting_query = query(
+        deps.as_ref(),
+        env,
+        QueryMsg::VestingInfo {
+            address: team.to_string(),
+        },
+    )
+    .unwrap();
+    let vesting_res: VestingInfoResponse = from_json(&vesting_query).unwrap();
+    assert_eq!(vesting_res.schedule, None);
+    assert_eq!(vesting_res.locked, Uint128::zero());
+}
+
+#[test]
+fn exempt_ecosystem_contracts() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    let stake = Addr::unchecked("stake");
+    let proxy = Addr::unchecked("proxy");
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: None,
+            transfer_tax: Some(FractionFormat {
+                numerator: Uint128::new(10),
+                denominator: Uint128::new(100),
+            }),
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![
+            Cw20Coin {
+                address: user.to_string(),
+                amount: Uint128::new(1000),
+            },
+            Cw20Coin {
+                address: proxy.to_string(),
+                amount: Uint128::new(1000),
+            },
+        ]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // fail to register with non-owner
+    let msg = ExecuteMsg::RegisterEcosystemContract {
+        address: stake.to_string(),
+        role: EcosystemRole::Stake,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("non_owner", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let owner_info = mock_info(owner.as_str(), &[]);
+    for (address, role) in [
+        (&stake, EcosystemRole::Stake),
+        (&proxy, EcosystemRole::Proxy),
+    ] {
+        let msg = ExecuteMsg::RegisterEcosystemContract {
+            address: address.to_string(),
+            role,
+        };
+        execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+    }
+
+    let contracts_query = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::EcosystemContracts {
+            role: Some(EcosystemRole::Stake),
+        },
+    )
+    .unwrap();
+    let contracts_res: EcosystemContractsResponse = from_json(&contracts_query).unwrap();
+    assert_eq!(
+        contracts_res.contracts,
+        vec![EcosystemContract {
+            address: stake.clone(),
+            role: EcosystemRole::Stake,
+        }]
+    );
+
+    let transfer =
+        |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>, from: &Addr, to: &Addr| {
+            let msg = ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount: Uint128::new(100),
+            };
+            let res = execute(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(from.as_str(), &[]),
+                msg,
+            )
+            .unwrap();
+            helpers::event_attribute(&res.events[0], "tax_category")
+        };
+
+    // stake is exempted in both directions
+    assert_eq!(transfer(&mut deps, &user, &stake), "none");
+    assert_eq!(transfer(&mut deps, &stake, &user), "none");
+    // proxy is only exempted when delivering tokens
+    assert_eq!(transfer(&mut deps, &proxy, &user), "none");
+    assert_eq!(transfer(&mut deps, &user, &proxy), "transfer");
+
+    // the tax free query follows the same rules
+    let tax_free = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier, Empty>, address: &Addr| {
+        let tax_free_query = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TaxFreeAddress {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+        from_json::<TaxFreeAddressResponse>(&tax_free_query).unwrap()
+    };
+    assert_eq!(
+        tax_free(&deps, &stake),
+        TaxFreeAddressResponse {
+            tax_free: true,
+            incoming: true,
+            outgoing: true,
+        }
+    );
+    assert_eq!(
+        tax_free(&deps, &proxy),
+        TaxFreeAddressResponse {
+            tax_free: false,
+            incoming: false,
+            outgoing: true,
+        }
+    );
+
+    // deregistered contracts are taxed again
+    let msg = ExecuteMsg::DeregisterEcosystemContract {
+        address: stake.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+    assert_eq!(transfer(&mut deps, &user, &stake), "transfer");
+}
+
+#[test]
+fn burn_collected_tax() {
+    let mut deps = mock_dependencies();
+    let owner = Addr::unchecked("owner-tax-free");
+    let seller = Addr::unchecked("seller");
+    let sell_tax = FractionFormat {
+        numerator: Uint128::new(40),
+        denominator: Uint128::new(100),
+    };
+    let init_msg = InstantiateMsg {
+        owner: owner.to_string(),
+        tax_info: TaxInfo {
+            buy_tax: None,
+            sell_tax: Some(sell_tax.clone()),
+            transfer_tax: None,
+        },
+        cw20_instantiate_msg: helpers::mock_cw20_instantiate_msg(vec![Cw20Coin {
+            address: seller.to_string(),
+            amount: Uint128::MAX,
+        }]),
+    };
+    helpers::setup_contract(&mut deps, init_msg).unwrap();
+
+    // fail to set burning contract with non-owner
+    let msg = ExecuteMsg::SetBurningContract {
+        burning_contract: Some("burning".to_string()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("non_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(owner.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+
+    let burning_query = query(deps.as_ref(), mock_env(), QueryMsg::BurningContract {}).unwrap();
+    let burning_res: BurningContractResponse = from_json(&burning_query).unwrap();
+    assert_eq!(
+        burning_res.burning_contract,
+        Some(Addr::unchecked("burning"))
+    );
+
+    // swap collected tax to this contract and burn it in reply
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked("cremat_token");
+    deps.querier
+        .update_balance("cremat_token", vec![Coin::new(1000, "uluna")]);
+    let sell_amount =
+        SWAP_COLLECTED_TAX_THRESHOLD * sell_tax.denominator / sell_tax.numerator + Uint128::one();
+    let msg = ExecuteMsg::Send {
+        contract: "terraswap_router".to_string(),
+        amount: sell_amount,
+        msg: Binary::default(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(seller.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+    let swap_msg = res
+        .messages
+        .iter()
+        .find(|sub_msg| sub_msg.id == BURN_COLLECTED_TAX_REPLY_ID)
+        .unwrap();
+    assert_eq!(swap_msg.reply_on, ReplyOn::Success);
+
+    deps.querier
+        .update_balance("cremat_token", vec![Coin::new(201000, "uluna")]);
+    let reply_msg = Reply {
+        id: BURN_COLLECTED_TAX_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+
+    // the burn is credited to the tax collector, not this contract
+    let collect_tax_query =
+        query(deps.as_ref(), mock_env(), QueryMsg::CollectTaxAddress {}).unwrap();
+    let collect_tax_res: CollectTaxAddressResponse = from_json(&collect_tax_query).unwrap();
+    assert_eq!(collect_tax_res.collect_tax_address, owner);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "burning".to_string(),
+            msg: to_json_binary(&BurningExecuteMsg::Burn {
+                recipient: Some(owner.to_string()),
+            })
+            .unwrap(),
+            funds: vec![Coin::new(199000, "uluna")],
+        })
+    );
+    assert_eq!(
+        helpers::event_attribute(&res.events[0], "recipient"),
+        owner.as_str()
+    );
+    assert_eq!(
+        helpers::event_attribute(&res.events[0], "swapped_amount"),
+        "200000"
+    );
+
+    // the owner sweeps what the send tax left over, keeping the tax of the sweep
+    let mut env = mock_env();
+    env.contract.address = Addr::unchecked("cremat_token");
+    deps.querier
+        .update_balance("cremat_token", vec![Coin::new(2000, "uluna")]);
+    let msg = ExecuteMsg::Sweep {
+        denom: "uluna".to_string(),
+        recipient: "treasury".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("non_owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury".to_string(),
+            amount: vec![Coin::new(1990, "uluna")],
+        })
+    );
+    assert_eq!(res.events[0].ty, "cremation_sweep");
+
+    let msg = ExecuteMsg::Sweep {
+        denom: "uusd".to_string(),
+        recipient: "treasury".to_string(),
+    };
+    let err = execute(deps.as_mut(), env, mock_info(owner.as_str(), &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("Nothing to sweep"))
+    );
+}
@@ -1,15 +1,18 @@
 use cosmwasm_std::{
-    from_json, to_json_binary, Binary, Coin, Decimal, Deps, DepsMut, Env, MessageInfo,
-    QueryRequest, Reply, Response, StdResult, SubMsg, WasmMsg, WasmQuery,
+    from_json, to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Order,
+    QueryRequest, Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 use cremation_token::msg::AssetInfo;
+use cremation_token::swap::{query_asset_balance, swap_run_msg, SwapRun};
 use cremation_token::{
     msg::{CollectTaxAddressResponse, QueryMsg as ExtendedCw20QueryMsg},
     state::FractionFormat,
 };
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
 
-use crate::{error::ContractError, helpers::create_swap_operations, msg::*, state::*};
+use cremation_token::msg::{SwapDex, SwapHop};
+
+use crate::{error::ContractError, helpers::create_swap_runs, msg::*, state::*};
 
 pub const SWAP_REPLY_ID: u64 = 1;
 pub const LUNC_TAX: Decimal = Decimal::permille(5);
@@ -24,7 +27,14 @@ pub fn instantiate(
     let swap_router = deps.api.addr_validate(&msg.swap_router)?;
 
     OWNER.save(deps.storage, &owner)?;
-    SWAP_ROUTER.save(deps.storage, &swap_router)?;
+    SWAP_ROUTERS.save(
+        deps.storage,
+        &SwapDex::Terraport.to_string(),
+        &SwapRouterInfo {
+            dex: SwapDex::Terraport,
+            router: swap_router,
+        },
+    )?;
     CACHE.save(
         deps.storage,
         &CacheData {
@@ -37,6 +47,22 @@ pub fn instantiate(
     Ok(Response::default())
 }
 
+pub fn migrate(deps: DepsMut) -> StdResult<Response> {
+    if let Some(swap_router) = SWAP_ROUTER.may_load(deps.storage)? {
+        SWAP_ROUTERS.save(
+            deps.storage,
+            &SwapDex::Terraport.to_string(),
+            &SwapRouterInfo {
+                dex: SwapDex::Terraport,
+                router: swap_router,
+            },
+        )?;
+        SWAP_ROUTER.remove(deps.storage);
+    }
+
+    Ok(Response::default())
+}
+
 pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     if msg.id != SWAP_REPLY_ID {
         return Err(ContractError::InvalidReplyMsg {});
@@ -47,6 +73,23 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractEr
         return Err(ContractError::AlreadyUnlocked {});
     }
 
+    // a run into an intermediate asset finished, swap its output through the next router
+    if let Some(pending) = PENDING_SWAP_RUNS.may_load(deps.storage)? {
+        let asset_info = pending.runs[0].offer_asset_info.clone();
+        let balance = query_asset_balance(&deps.querier, &asset_info, &env.contract.address)?;
+        let received = balance
+            .checked_sub(pending.pre_swap_balance)
+            .map_err(StdError::from)?;
+        let amount = match asset_info {
+            AssetInfo::NativeToken { .. } => received - received * LUNC_TAX,
+            AssetInfo::Token { .. } => received,
+        };
+        if amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        return execute::swap_next_run(deps, &env, pending.runs, amount);
+    }
+
     let token = cached_data.token_address.clone();
     cached_data.locked = false;
     CACHE.save(deps.storage, &cached_data)?;
@@ -118,8 +161,8 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdateOwner { new_owner } => execute::update_owner(deps, env, info, new_owner),
-        ExecuteMsg::UpdateSwapRouter { router } => {
-            execute::update_swap_router(deps, env, info, router)
+        ExecuteMsg::UpdateSwapRouter { dex, router } => {
+            execute::update_swap_router(deps, env, info, dex, router)
         }
         ExecuteMsg::SetTokenBuyTax {
             token_address,
@@ -167,6 +210,7 @@ pub mod execute {
         deps: DepsMut,
         _env: Env,
         info: MessageInfo,
+        dex: SwapDex,
         router: String,
     ) -> Result<Response, ContractError> {
         let owner = OWNER.load(deps.storage)?;
@@ -175,9 +219,18 @@ pub mod execute {
         }
 
         let swap_router = deps.api.addr_validate(&router)?;
-        SWAP_ROUTER.save(deps.storage, &swap_router)?;
+        SWAP_ROUTERS.save(
+            deps.storage,
+            &dex.to_string(),
+            &SwapRouterInfo {
+                dex: dex.clone(),
+                router: swap_router.clone(),
+            },
+        )?;
 
-        Ok(Response::new().add_attribute("swap_router", swap_router))
+        Ok(Response::new()
+            .add_attribute("dex", dex.to_string())
+            .add_attribute("swap_router", swap_router))
     }
 
     pub fn set_token_tax_info(
@@ -208,10 +261,10 @@ pub mod execute {
 
     pub fn swap(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         ask_asset: AssetInfo,
-        swap_paths: Vec<AssetInfo>,
+        swap_paths: Vec<SwapHop>,
     ) -> Result<Response, ContractError> {
         if info.funds.len() != 1 {
             return Err(ContractError::ExpectOnlyOneCoin {});
@@ -222,7 +275,12 @@ pub mod execute {
             return Err(ContractError::ZeroAmount {});
         }
 
-        match ask_asset.clone() {
+        let offer_asset = AssetInfo::NativeToken {
+            denom: denom.clone(),
+        };
+        let swap_runs = create_swap_runs(deps.storage, offer_asset, &ask_asset, swap_paths)?;
+
+        match ask_asset {
             AssetInfo::Token { contract_addr } => {
                 let cached_data = CACHE.load(deps.storage)?;
                 if cached_data.locked {
@@ -241,31 +299,44 @@ pub mod execute {
             _ => return Err(ContractError::InvalidAskAsset {}),
         }
 
-        let offer_asset = AssetInfo::NativeToken {
-            denom: denom.clone(),
-        };
-        let swap_operations = create_swap_operations(offer_asset, ask_asset, swap_paths);
-        let swap_router = SWAP_ROUTER.load(deps.storage)?;
-
         let tax = swap_amount * LUNC_TAX;
         let actual_swap_amount = swap_amount - tax;
+        swap_next_run(deps, &env, swap_runs, actual_swap_amount)
+    }
 
-        let swap_msg = WasmMsg::Execute {
-            contract_addr: swap_router.to_string(),
-            msg: to_json_binary(&swap_operations).unwrap(),
-            funds: vec![Coin {
-                denom,
-                amount: actual_swap_amount,
-            }],
-        };
-        let swap_submsg = SubMsg::reply_on_success(swap_msg, SWAP_REPLY_ID);
+    // swap `amount` through the next run, keeping the later runs to chain in reply
+    pub fn swap_next_run(
+        deps: DepsMut,
+        env: &Env,
+        mut swap_runs: Vec<SwapRun>,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        let swap_run = swap_runs.remove(0);
+        if swap_runs.is_empty() {
+            PENDING_SWAP_RUNS.remove(deps.storage);
+        } else {
+            let pre_swap_balance = query_asset_balance(
+                &deps.querier,
+                &swap_run.ask_asset_info,
+                &env.contract.address,
+            )?;
+            PENDING_SWAP_RUNS.save(
+                deps.storage,
+                &PendingSwapRuns {
+                    runs: swap_runs,
+                    pre_swap_balance,
+                },
+            )?;
+        }
 
+        let swap_msg = swap_run_msg(&swap_run, amount, None, None)?;
+        let swap_submsg = SubMsg::reply_on_success(swap_msg, SWAP_REPLY_ID);
         Ok(Response::new().add_submessage(swap_submsg))
     }
 
     pub fn receive_cw20(
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         cw20_msg: Cw20ReceiveMsg,
     ) -> Result<Response, ContractError> {
@@ -277,14 +348,19 @@ pub mod execute {
                 ask_asset,
                 swap_paths,
             }) => {
-                match ask_asset.clone() {
+                let offer_asset = AssetInfo::Token {
+                    contract_addr: token_in.to_string(),
+                };
+                let swap_runs =
+                    create_swap_runs(deps.storage, offer_asset, &ask_asset, swap_paths)?;
+
+                match ask_asset {
                     AssetInfo::Token { contract_addr } => {
                         let cached_data = CACHE.load(deps.storage)?;
                         if cached_data.locked {
                             return Err(ContractError::Locked {});
                         }
                         let buyer = deps.api.addr_validate(&cw20_msg.sender)?;
-                        println!("buyer: {:?}", buyer);
                         let token_address = deps.api.addr_validate(&contract_addr)?;
                         CACHE.save(
                             deps.storage,
@@ -298,23 +374,7 @@ pub mod execute {
                     _ => return Err(ContractError::InvalidAskAsset {}),
                 }
 
-                let offer_asset = AssetInfo::Token {
-                    contract_addr: token_in.to_string(),
-                };
-                let swap_operations = create_swap_operations(offer_asset, ask_asset, swap_paths);
-                let swap_router = SWAP_ROUTER.load(deps.storage)?;
-                let cw20_send_msg = WasmMsg::Execute {
-                    contract_addr: token_in.to_string(),
-                    msg: to_json_binary(&Cw20ExecuteMsg::Send {
-                        contract: swap_router.to_string(),
-                        amount,
-                        msg: to_json_binary(&swap_operations).unwrap(),
-                    })?,
-                    funds: vec![],
-                };
-                let cw20_send_submsg = SubMsg::reply_on_success(cw20_send_msg, SWAP_REPLY_ID);
-
-                Ok(Response::new().add_submessage(cw20_send_submsg))
+                swap_next_run(deps, &env, swap_runs, amount)
             }
             Err(err) => Err(ContractError::StdError(err)),
         }
@@ -341,7 +401,10 @@ pub mod query {
     }
 
     pub fn swap_router(deps: Deps) -> StdResult<SwapRouterResponse> {
-        let router = SWAP_ROUTER.load(deps.storage)?;
-        Ok(SwapRouterResponse { router })
+        let routers = SWAP_ROUTERS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, router)| router))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(SwapRouterResponse { routers })
     }
 }
@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cremation_token::swap::SwapPathError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -21,4 +22,18 @@ pub enum ContractError {
     InvalidTaxRate {},
     #[error("Invalid Ask Asset")]
     InvalidAskAsset {},
+    #[error("Invalid Swap Paths")]
+    InvalidSwapPaths {},
+    #[error("Swap Router Not Set For {dex}")]
+    SwapRouterNotSet { dex: String },
+}
+
+impl From<SwapPathError> for ContractError {
+    fn from(err: SwapPathError) -> Self {
+        match err {
+            SwapPathError::StdError(err) => ContractError::StdError(err),
+            SwapPathError::InvalidSwapPaths {} => ContractError::InvalidSwapPaths {},
+            SwapPathError::SwapRouterNotSet { dex } => ContractError::SwapRouterNotSet { dex },
+        }
+    }
 }
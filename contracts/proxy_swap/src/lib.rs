@@ -11,8 +11,8 @@ pub mod msg;
 pub mod state;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    contract::migrate(deps)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -1,6 +1,11 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::Addr;
-use cremation_token::{msg::AssetInfo, state::FractionFormat};
+use cremation_token::{
+    msg::{AssetInfo, SwapDex, SwapHop},
+    state::FractionFormat,
+};
+
+use crate::state::SwapRouterInfo;
 use cw20::Cw20ReceiveMsg;
 
 #[cw_serde]
@@ -10,7 +15,7 @@ pub struct MigrateMsg {}
 pub enum Cw20HookMsg {
     Swap {
         ask_asset: AssetInfo,
-        swap_paths: Vec<AssetInfo>,
+        swap_paths: Vec<SwapHop>,
     },
 }
 
@@ -26,6 +31,7 @@ pub enum ExecuteMsg {
         new_owner: String,
     },
     UpdateSwapRouter {
+        dex: SwapDex,
         router: String,
     },
     SetTokenBuyTax {
@@ -35,7 +41,7 @@ pub enum ExecuteMsg {
     Receive(Cw20ReceiveMsg),
     Swap {
         ask_asset: AssetInfo,
-        swap_paths: Vec<AssetInfo>,
+        swap_paths: Vec<SwapHop>,
     },
 }
 
@@ -63,5 +69,5 @@ pub struct TokenBuyTaxResponse {
 
 #[cw_serde]
 pub struct SwapRouterResponse {
-    pub router: Addr,
+    pub routers: Vec<SwapRouterInfo>,
 }
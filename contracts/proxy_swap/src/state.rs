@@ -1,11 +1,19 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cremation_token::state::FractionFormat;
+use cosmwasm_std::{Addr, Uint128};
+use cremation_token::{msg::SwapDex, state::FractionFormat, swap::SwapRun};
 use cw_storage_plus::{Item, Map};
 
 pub const OWNER: Item<Addr> = Item::new("owner");
+/// Legacy single router, migrated into `SWAP_ROUTERS` as the TerraPort router.
 pub const SWAP_ROUTER: Item<Addr> = Item::new("swap_router");
 
+#[cw_serde]
+pub struct SwapRouterInfo {
+    pub dex: SwapDex,
+    pub router: Addr,
+}
+pub const SWAP_ROUTERS: Map<&str, SwapRouterInfo> = Map::new("swap_routers");
+
 pub const TOKEN_BUY_TAX: Map<Addr, FractionFormat> = Map::new("token_buy_tax");
 
 #[cw_serde]
@@ -15,3 +23,12 @@ pub struct CacheData {
     pub token_address: Addr,
 }
 pub const CACHE: Item<CacheData> = Item::new("cache");
+
+// runs through other routers still to swap while a swap is chained, with the balance of the
+// ask asset of the running run before it
+#[cw_serde]
+pub struct PendingSwapRuns {
+    pub runs: Vec<SwapRun>,
+    pub pre_swap_balance: Uint128,
+}
+pub const PENDING_SWAP_RUNS: Item<PendingSwapRuns> = Item::new("pending_swap_runs");
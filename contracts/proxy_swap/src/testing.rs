@@ -6,7 +6,10 @@ use cosmwasm_std::{
     SubMsgResult, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
 use cremation_token::{
-    msg::{AssetInfo, CollectTaxAddressResponse, QueryMsg as ExtendedCw20QueryMsg},
+    msg::{
+        AssetInfo, CollectTaxAddressResponse, QueryMsg as ExtendedCw20QueryMsg, RouterExecuteMsg,
+        SwapDex, SwapHop, SwapOperation,
+    },
     state::FractionFormat,
 };
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg};
@@ -18,6 +21,7 @@ use crate::{
         Cw20HookMsg, ExecuteMsg, InstantiateMsg, OwnerResponse, QueryMsg, SwapRouterResponse,
         TokenBuyTaxResponse,
     },
+    state::SwapRouterInfo,
 };
 
 mod helpers {
@@ -133,7 +137,13 @@ fn init_properly() {
 
     let swap_router_query = query(deps.as_ref(), env.clone(), QueryMsg::SwapRouter {}).unwrap();
     let swap_router_res: SwapRouterResponse = from_json(&swap_router_query).unwrap();
-    assert_eq!(swap_router_res.router, swap_router);
+    assert_eq!(
+        swap_router_res.routers,
+        vec![SwapRouterInfo {
+            dex: SwapDex::Terraport,
+            router: Addr::unchecked(swap_router),
+        }]
+    );
 }
 
 #[test]
@@ -210,6 +220,7 @@ fn update_swap_router_properly() {
 
     let update_swap_router_info = mock_info(owner.as_str(), &[]);
     let update_swap_router_msg = ExecuteMsg::UpdateSwapRouter {
+        dex: SwapDex::Terraswap,
         router: new_swap_router.clone(),
     };
     execute(
@@ -222,7 +233,19 @@ fn update_swap_router_properly() {
 
     let swap_router_query = query(deps.as_ref(), env.clone(), QueryMsg::SwapRouter {}).unwrap();
     let swap_router_res: SwapRouterResponse = from_json(&swap_router_query).unwrap();
-    assert_eq!(swap_router_res.router, new_swap_router);
+    assert_eq!(
+        swap_router_res.routers,
+        vec![
+            SwapRouterInfo {
+                dex: SwapDex::Terraport,
+                router: Addr::unchecked(swap_router),
+            },
+            SwapRouterInfo {
+                dex: SwapDex::Terraswap,
+                router: Addr::unchecked(new_swap_router),
+            },
+        ]
+    );
 }
 
 #[test]
@@ -241,6 +264,7 @@ fn update_swap_router_without_authorized() {
 
     let update_swap_router_info = mock_info("random", &[]);
     let update_swap_router_msg = ExecuteMsg::UpdateSwapRouter {
+        dex: SwapDex::Terraswap,
         router: new_swap_router.clone(),
     };
     let res = execute(
@@ -360,7 +384,12 @@ fn swap_native_properly() {
         ask_asset: AssetInfo::Token {
             contract_addr: token_address.to_string(),
         },
-        swap_paths: vec![],
+        swap_paths: vec![SwapHop {
+            dex: SwapDex::Terraport,
+            ask_asset_info: AssetInfo::Token {
+                contract_addr: token_address.to_string(),
+            },
+        }],
     };
     let res = execute(deps.as_mut(), env.clone(), swap_info, swap_msg).unwrap();
     assert_eq!(res.messages.len(), 1);
@@ -470,7 +499,12 @@ fn swap_cw20_properly() {
             ask_asset: AssetInfo::Token {
                 contract_addr: ask_token_address.to_string(),
             },
-            swap_paths: vec![],
+            swap_paths: vec![SwapHop {
+                dex: SwapDex::Terraport,
+                ask_asset_info: AssetInfo::Token {
+                    contract_addr: ask_token_address.to_string(),
+                },
+            }],
         })
         .unwrap(),
         sender: buyer.to_string(),
@@ -547,3 +581,207 @@ fn swap_cw20_properly() {
         .unwrap();
     assert_eq!(attrs.value, tax_amount.to_string());
 }
+
+#[test]
+fn swap_with_dex_per_hop() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = "owner";
+    let swap_router = "router";
+    let token_address = "token_address";
+    let buyer = "buyer";
+    helpers::setup_contract(
+        &mut deps,
+        env.clone(),
+        owner,
+        swap_router,
+        token_address,
+        FractionFormat {
+            numerator: Uint128::zero(),
+            denominator: Uint128::from(100u64),
+        },
+    );
+
+    let uusd = AssetInfo::NativeToken {
+        denom: "uusd".to_string(),
+    };
+    let token = AssetInfo::Token {
+        contract_addr: token_address.to_string(),
+    };
+    let swap_info = mock_info(
+        buyer,
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+    let swap_paths = vec![
+        SwapHop {
+            dex: SwapDex::Native,
+            ask_asset_info: uusd.clone(),
+        },
+        SwapHop {
+            dex: SwapDex::Terraswap,
+            ask_asset_info: token.clone(),
+        },
+    ];
+
+    // the terraswap router is not set yet
+    let swap_msg = ExecuteMsg::Swap {
+        ask_asset: token.clone(),
+        swap_paths: swap_paths.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), swap_info.clone(), swap_msg);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::SwapRouterNotSet {
+            dex: "native".to_string()
+        }
+    );
+
+    for dex in [SwapDex::Native, SwapDex::Terraswap] {
+        let update_swap_router_msg = ExecuteMsg::UpdateSwapRouter {
+            dex,
+            router: swap_router.to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            update_swap_router_msg,
+        )
+        .unwrap();
+    }
+
+    // the last hop must end in the ask asset
+    let swap_msg = ExecuteMsg::Swap {
+        ask_asset: token.clone(),
+        swap_paths: swap_paths[..1].to_vec(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), swap_info.clone(), swap_msg);
+    assert_eq!(res.unwrap_err(), ContractError::InvalidSwapPaths {});
+
+    let swap_msg = ExecuteMsg::Swap {
+        ask_asset: token.clone(),
+        swap_paths: swap_paths.clone(),
+    };
+    let res = execute(deps.as_mut(), env.clone(), swap_info.clone(), swap_msg).unwrap();
+    match res.messages[0].msg.clone() {
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) => {
+            assert_eq!(contract_addr, swap_router);
+            assert_eq!(
+                from_json::<RouterExecuteMsg>(&msg).unwrap(),
+                RouterExecuteMsg::ExecuteSwapOperations {
+                    operations: vec![
+                        SwapOperation::NativeSwap {
+                            offer_denom: "uluna".to_string(),
+                            ask_denom: "uusd".to_string(),
+                        },
+                        SwapOperation::TerraSwap {
+                            offer_asset_info: uusd.clone(),
+                            ask_asset_info: token.clone(),
+                        },
+                    ],
+                    to: None,
+                    minimum_receive: None,
+                    deadline: None,
+                }
+            );
+        }
+        _ => panic!("Unexpected sub message"),
+    }
+
+    // hops through different routers are chained in reply
+    let mut deps = mock_dependencies();
+    helpers::setup_contract(
+        &mut deps,
+        env.clone(),
+        owner,
+        swap_router,
+        token_address,
+        FractionFormat {
+            numerator: Uint128::zero(),
+            denominator: Uint128::from(100u64),
+        },
+    );
+    for (dex, router) in [
+        (SwapDex::Native, "native_router"),
+        (SwapDex::Terraswap, "router"),
+    ] {
+        let update_swap_router_msg = ExecuteMsg::UpdateSwapRouter {
+            dex,
+            router: router.to_string(),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            update_swap_router_msg,
+        )
+        .unwrap();
+    }
+    let swap_msg = ExecuteMsg::Swap {
+        ask_asset: token.clone(),
+        swap_paths,
+    };
+    let res = execute(deps.as_mut(), env.clone(), swap_info, swap_msg).unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "native_router".to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: vec![SwapOperation::NativeSwap {
+                    offer_denom: "uluna".to_string(),
+                    ask_denom: "uusd".to_string(),
+                }],
+                minimum_receive: None,
+                to: None,
+                deadline: None,
+            })
+            .unwrap(),
+            funds: vec![Coin {
+                denom: "uluna".to_string(),
+                amount: Uint128::from(100u128),
+            }],
+        })
+    );
+
+    deps.querier.update_balance(
+        env.contract.address.clone(),
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(80u128),
+        }],
+    );
+    let reply_msg = Reply {
+        id: SWAP_REPLY_ID,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    };
+    let res = reply(deps.as_mut(), env, reply_msg).unwrap();
+    assert_eq!(res.messages[0].id, SWAP_REPLY_ID);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+                operations: vec![SwapOperation::TerraSwap {
+                    offer_asset_info: uusd,
+                    ask_asset_info: token,
+                }],
+                minimum_receive: None,
+                to: None,
+                deadline: None,
+            })
+            .unwrap(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(80u128),
+            }],
+        })
+    );
+}